@@ -0,0 +1,81 @@
+/// Removes ANSI escape sequences (CSI such as SGR colors, OSC such as hyperlinks,
+/// and two-byte ESC sequences) so colorized tool output reads cleanly in Slack.
+/// A sequence cut off at the end of the input is dropped rather than left half-printed.
+pub fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            // CSI: parameter and intermediate bytes, ended by a final byte in @..~
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: ended by BEL or ST (ESC \)
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            // Any other escape is ESC plus a single byte
+            Some(_) | None => {}
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strips_sgr_colors() {
+        let input = "\x1b[1;31merror\x1b[0m: build \x1b[32mpassed\x1b[m";
+        assert_eq!(strip_ansi(input), "error: build passed");
+    }
+
+    #[test]
+    fn test_strips_cursor_and_erase_sequences() {
+        assert_eq!(strip_ansi("\x1b[2K\x1b[1Gprogress 100%"), "progress 100%");
+    }
+
+    #[test]
+    fn test_strips_osc_hyperlinks() {
+        let input = "see \x1b]8;;https://example.com\x1b\\docs\x1b]8;;\x07 now";
+        assert_eq!(strip_ansi(input), "see docs now");
+    }
+
+    #[test]
+    fn test_plain_brackets_pass_through() {
+        let input = "[INFO] array[0] = [1, 2] and [31m is not an escape";
+        assert_eq!(strip_ansi(input), input);
+    }
+
+    #[test]
+    fn test_incomplete_sequences_at_end_are_dropped() {
+        assert_eq!(strip_ansi("done\x1b[1;3"), "done");
+        assert_eq!(strip_ansi("done\x1b"), "done");
+        assert_eq!(strip_ansi("done\x1b]8;;http://x"), "done");
+    }
+
+    #[test]
+    fn test_unicode_preserved() {
+        assert_eq!(strip_ansi("\x1b[33m⚠ déjà vu\x1b[0m ✅"), "⚠ déjà vu ✅");
+    }
+}
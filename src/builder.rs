@@ -0,0 +1,193 @@
+use crate::slack::{Block, ContextBlock, DividerBlock, HeaderBlock, SectionBlock};
+use crate::{
+    grapheme_len, resolve_color, SendConfig, SlackCliError, HEADER_TEXT_MAX, MESSAGE_BLOCKS_MAX,
+    SECTION_TEXT_MAX,
+};
+
+/// Assembles a Block Kit message for library callers, checking Slack's limits as it goes.
+///
+/// The first violation is kept and returned by [`MessageBuilder::build`], so calls
+/// can be chained without handling a `Result` at every step.
+///
+/// ```
+/// use slack_cli::{build_payload, MessageBuilder};
+///
+/// let config = MessageBuilder::new()
+///     .header("Deploy finished")
+///     .section("*api* v1.2.3 is live in prod")
+///     .context("sent by CI")
+///     .color("good")
+///     .build("#deploys")
+///     .unwrap();
+///
+/// let blocks = config.blocks.as_ref().unwrap();
+/// assert_eq!(blocks.len(), 3);
+/// assert_eq!(blocks[0]["type"], "header");
+/// assert_eq!(blocks[1]["text"]["text"], "*api* v1.2.3 is live in prod");
+/// assert_eq!(blocks[2]["type"], "context");
+///
+/// let built = build_payload(&config, None).unwrap();
+/// assert_eq!(built.resolved_color.as_deref(), Some("#36a64f"));
+/// ```
+#[derive(Default)]
+pub struct MessageBuilder {
+    blocks: Vec<Block>,
+    /// Section texts, joined into the top-level notification text
+    text: Vec<String>,
+    color: Option<String>,
+    error: Option<SlackCliError>,
+}
+
+impl MessageBuilder {
+    pub fn new() -> Self {
+        MessageBuilder::default()
+    }
+
+    /// Adds a plain-text header; at most `HEADER_TEXT_MAX` characters
+    pub fn header(self, text: &str) -> Self {
+        let len = grapheme_len(text);
+        if len > HEADER_TEXT_MAX {
+            return self.fail(SlackCliError::TitleTooLong(len));
+        }
+        self.push(Block::Header(HeaderBlock::new(text)))
+    }
+
+    /// Adds a mrkdwn section; at most `SECTION_TEXT_MAX` characters
+    pub fn section(mut self, text: &str) -> Self {
+        let len = grapheme_len(text);
+        if len > SECTION_TEXT_MAX {
+            return self.fail(SlackCliError::InvalidInput(format!(
+                "section text is {len} characters; Slack allows at most {SECTION_TEXT_MAX}"
+            )));
+        }
+        self.text.push(text.to_string());
+        self.push(Block::Section(SectionBlock::new(text)))
+    }
+
+    pub fn divider(self) -> Self {
+        self.push(Block::Divider(DividerBlock::new()))
+    }
+
+    /// Adds a muted context line
+    pub fn context(self, text: &str) -> Self {
+        self.push(Block::Context(ContextBlock::new(&[text.to_string()])))
+    }
+
+    /// Sends the blocks in an attachment with this hex color or keyword
+    pub fn color(mut self, color: &str) -> Self {
+        if let Err(e) = resolve_color(color) {
+            return self.fail(e);
+        }
+        self.color = Some(color.to_string());
+        self
+    }
+
+    /// A `SendConfig` for `channel` carrying the blocks, or the first limit that was broken.
+    /// The token and any other options are left for the caller to set.
+    pub fn build(self, channel: &str) -> Result<SendConfig, SlackCliError> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        let blocks = self
+            .blocks
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| SlackCliError::SerializationError(e.to_string()))?;
+        Ok(SendConfig {
+            channel: channel.to_string(),
+            message: self.text.join("\n"),
+            color: self.color,
+            blocks: Some(blocks),
+            ..Default::default()
+        })
+    }
+
+    fn push(mut self, block: Block) -> Self {
+        if self.blocks.len() == MESSAGE_BLOCKS_MAX {
+            return self.fail(SlackCliError::InvalidInput(format!(
+                "a message holds at most {MESSAGE_BLOCKS_MAX} blocks"
+            )));
+        }
+        self.blocks.push(block);
+        self
+    }
+
+    /// Records the first error; later ones are usually knock-on effects
+    fn fail(mut self, error: SlackCliError) -> Self {
+        self.error.get_or_insert(error);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builds_blocks_in_order() {
+        let config = MessageBuilder::new()
+            .header("Nightly report")
+            .section("All jobs passed")
+            .divider()
+            .section("Next run at 02:00")
+            .build("#reports")
+            .unwrap();
+
+        assert_eq!(config.channel, "#reports");
+        assert_eq!(config.message, "All jobs passed\nNext run at 02:00");
+        assert_eq!(config.color, None);
+        let types: Vec<&str> = config
+            .blocks
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|block| block["type"].as_str().unwrap())
+            .collect();
+        assert_eq!(types, vec!["header", "section", "divider", "section"]);
+    }
+
+    #[test]
+    fn test_section_over_limit_rejected() {
+        let result = MessageBuilder::new()
+            .section(&"a".repeat(SECTION_TEXT_MAX + 1))
+            .build("#ops");
+        assert!(
+            matches!(result, Err(SlackCliError::InvalidInput(ref msg)) if msg.contains("3000"))
+        );
+        assert!(MessageBuilder::new()
+            .section(&"a".repeat(SECTION_TEXT_MAX))
+            .build("#ops")
+            .is_ok());
+    }
+
+    #[test]
+    fn test_header_over_limit_rejected() {
+        let result = MessageBuilder::new()
+            .header(&"t".repeat(HEADER_TEXT_MAX + 1))
+            .section("body")
+            .build("#ops");
+        assert!(matches!(result, Err(SlackCliError::TitleTooLong(151))));
+    }
+
+    #[test]
+    fn test_block_count_capped() {
+        let builder = (0..MESSAGE_BLOCKS_MAX).fold(MessageBuilder::new(), |b, _| b.divider());
+        assert!(builder.build("#ops").is_ok());
+
+        let builder = (0..=MESSAGE_BLOCKS_MAX).fold(MessageBuilder::new(), |b, _| b.divider());
+        assert!(matches!(
+            builder.build("#ops"),
+            Err(SlackCliError::InvalidInput(ref msg)) if msg.contains("100 blocks")
+        ));
+    }
+
+    #[test]
+    fn test_first_error_wins() {
+        let result = MessageBuilder::new()
+            .color("not-a-color")
+            .header(&"t".repeat(HEADER_TEXT_MAX + 1))
+            .build("#ops");
+        assert!(matches!(result, Err(SlackCliError::InvalidColor(_))));
+    }
+}
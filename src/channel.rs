@@ -0,0 +1,416 @@
+use crate::slack::{Conversation, SlackClient};
+use crate::{OutputFormat, SlackCliError};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+pub const DEFAULT_CONVERSATION_TYPES: &str = "public_channel,private_channel";
+
+/// Fetches every conversation visible to the token, following cursor pagination.
+pub fn list_all_conversations(
+    client: &dyn SlackClient,
+    token: &str,
+    types: &str,
+) -> Result<Vec<Conversation>, SlackCliError> {
+    let mut conversations = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let response = client.list_conversations(token, types, cursor.as_deref())?;
+        if !response.ok {
+            let error_msg = response
+                .error
+                .unwrap_or_else(|| "unknown error".to_string());
+            return Err(SlackCliError::SlackApiError(error_msg));
+        }
+
+        cursor = response.next_cursor().map(str::to_string);
+        conversations.extend(response.channels);
+
+        if cursor.is_none() {
+            break;
+        }
+    }
+
+    Ok(conversations)
+}
+
+/// Slack channel/group/DM IDs: an uppercase C, G or D followed by uppercase alphanumerics
+fn looks_like_id(channel: &str) -> bool {
+    let mut chars = channel.chars();
+    matches!(chars.next(), Some('C' | 'G' | 'D'))
+        && channel.len() >= 9
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
+/// Applies `--channel-prefix`/`--channel-suffix` to a channel name, giving
+/// `#<prefix><name><suffix>`. Channel IDs and `@user` targets pass through unchanged.
+pub fn decorate_channel(channel: &str, prefix: Option<&str>, suffix: Option<&str>) -> String {
+    if (prefix.is_none() && suffix.is_none()) || channel.starts_with('@') || looks_like_id(channel)
+    {
+        return channel.to_string();
+    }
+
+    let name = channel.strip_prefix('#').unwrap_or(channel);
+    format!(
+        "#{}{name}{}",
+        prefix.unwrap_or_default(),
+        suffix.unwrap_or_default()
+    )
+}
+
+/// Opens the DM with `users` (a group DM for several) and returns its channel ID.
+pub fn open_dm(
+    client: &dyn SlackClient,
+    token: &str,
+    users: &[String],
+) -> Result<String, SlackCliError> {
+    let response = client.open_conversation(token, users)?;
+    match response.channel {
+        Some(channel) if response.ok => Ok(channel.id),
+        _ => Err(SlackCliError::ConversationOpenFailed {
+            users: users.join(","),
+            error: response
+                .error
+                .unwrap_or_else(|| "unknown error".to_string()),
+        }),
+    }
+}
+
+/// Renders conversations as `ID<TAB>#name<TAB>private|public` rows, or as a JSON array.
+pub fn format_conversations(
+    conversations: &[Conversation],
+    format: OutputFormat,
+) -> Result<String, SlackCliError> {
+    match format {
+        OutputFormat::Text => Ok(conversations
+            .iter()
+            .map(|c| {
+                let name = c
+                    .name
+                    .as_deref()
+                    .map(|n| format!("#{n}"))
+                    .unwrap_or_default();
+                let visibility = if c.is_private { "private" } else { "public" };
+                format!("{}\t{name}\t{visibility}\n", c.id)
+            })
+            .collect()),
+        OutputFormat::Json => serde_json::to_string_pretty(conversations)
+            .map_err(|e| SlackCliError::SerializationError(e.to_string())),
+    }
+}
+
+/// Maps `#name` channels to their IDs, listing conversations at most once per resolver.
+pub struct ChannelResolver {
+    types: String,
+    cache: RefCell<Option<HashMap<String, String>>>,
+}
+
+impl ChannelResolver {
+    pub fn new(types: &str) -> Self {
+        ChannelResolver {
+            types: types.to_string(),
+            cache: RefCell::new(None),
+        }
+    }
+
+    /// Returns the channel ID for `#name`; anything else is assumed to already be an ID.
+    pub fn resolve(
+        &self,
+        client: &dyn SlackClient,
+        token: &str,
+        channel: &str,
+    ) -> Result<String, SlackCliError> {
+        let Some(name) = channel.strip_prefix('#') else {
+            return Ok(channel.to_string());
+        };
+
+        if self.cache.borrow().is_none() {
+            let ids = list_all_conversations(client, token, &self.types)?
+                .into_iter()
+                .filter_map(|c| c.name.map(|name| (name, c.id)))
+                .collect();
+            *self.cache.borrow_mut() = Some(ids);
+        }
+
+        self.cache
+            .borrow()
+            .as_ref()
+            .and_then(|ids| ids.get(name).cloned())
+            .ok_or_else(|| SlackCliError::ChannelNotFound(channel.to_string()))
+    }
+}
+
+impl Default for ChannelResolver {
+    fn default() -> Self {
+        ChannelResolver::new(DEFAULT_CONVERSATION_TYPES)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::slack::{ConversationOpenResponse, ConversationsListResponse, SlackResponse};
+    use std::cell::{Cell, RefCell};
+
+    struct MockListClient {
+        pages: Vec<&'static str>,
+        calls: Cell<usize>,
+    }
+
+    impl MockListClient {
+        fn new(pages: Vec<&'static str>) -> Self {
+            MockListClient {
+                pages,
+                calls: Cell::new(0),
+            }
+        }
+    }
+
+    impl SlackClient for MockListClient {
+        fn post_message(
+            &self,
+            _token: &str,
+            _payload: &[u8],
+        ) -> Result<SlackResponse, SlackCliError> {
+            panic!("chat.postMessage not expected in this test")
+        }
+
+        fn list_conversations(
+            &self,
+            _token: &str,
+            _types: &str,
+            cursor: Option<&str>,
+        ) -> Result<ConversationsListResponse, SlackCliError> {
+            let page = self.calls.get();
+            self.calls.set(page + 1);
+            let expected_cursor = (page > 0).then(|| format!("page{page}"));
+            assert_eq!(cursor, expected_cursor.as_deref());
+            Ok(serde_json::from_str(self.pages[page]).unwrap())
+        }
+    }
+
+    /// Answers conversations.open with a canned JSON response, recording the users
+    struct MockOpenClient {
+        response: &'static str,
+        users: RefCell<Vec<String>>,
+    }
+
+    impl MockOpenClient {
+        fn new(response: &'static str) -> Self {
+            MockOpenClient {
+                response,
+                users: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl SlackClient for MockOpenClient {
+        fn post_message(
+            &self,
+            _token: &str,
+            _payload: &[u8],
+        ) -> Result<SlackResponse, SlackCliError> {
+            panic!("chat.postMessage not expected in this test")
+        }
+
+        fn list_conversations(
+            &self,
+            _token: &str,
+            _types: &str,
+            _cursor: Option<&str>,
+        ) -> Result<ConversationsListResponse, SlackCliError> {
+            panic!("conversations.list not expected in this test")
+        }
+
+        fn open_conversation(
+            &self,
+            _token: &str,
+            users: &[String],
+        ) -> Result<ConversationOpenResponse, SlackCliError> {
+            self.users.borrow_mut().extend_from_slice(users);
+            Ok(serde_json::from_str(self.response).unwrap())
+        }
+    }
+
+    const PAGE_1: &str = r#"{"ok": true, "channels": [
+        {"id": "C111", "name": "general"},
+        {"id": "C222", "name": "random"}
+    ], "response_metadata": {"next_cursor": "page1"}}"#;
+
+    const PAGE_2: &str = r#"{"ok": true, "channels": [
+        {"id": "G333", "name": "ops-private", "is_private": true}
+    ], "response_metadata": {"next_cursor": ""}}"#;
+
+    #[test]
+    fn test_open_dm_returns_channel_id() {
+        let client = MockOpenClient::new(r#"{"ok": true, "channel": {"id": "D0123"}}"#);
+        let users = vec!["U012345".to_string()];
+        assert_eq!(open_dm(&client, "xoxb-test", &users).unwrap(), "D0123");
+        assert_eq!(*client.users.borrow(), users);
+    }
+
+    #[test]
+    fn test_open_dm_failure() {
+        let client = MockOpenClient::new(r#"{"ok": false, "error": "user_not_found"}"#);
+        let users = vec!["U1".to_string(), "U2".to_string()];
+        let err = open_dm(&client, "xoxb-test", &users).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "could not open a DM with U1,U2: user_not_found"
+        );
+    }
+
+    #[test]
+    fn test_list_all_conversations_follows_cursor() {
+        let client = MockListClient::new(vec![PAGE_1, PAGE_2]);
+        let conversations =
+            list_all_conversations(&client, "xoxb-test", DEFAULT_CONVERSATION_TYPES).unwrap();
+        assert_eq!(client.calls.get(), 2);
+        assert_eq!(conversations.len(), 3);
+        assert_eq!(conversations[2].id, "G333");
+    }
+
+    #[test]
+    fn test_list_all_conversations_api_error() {
+        let client = MockListClient::new(vec![r#"{"ok": false, "error": "missing_scope"}"#]);
+        let result = list_all_conversations(&client, "xoxb-test", DEFAULT_CONVERSATION_TYPES);
+        assert!(matches!(result, Err(SlackCliError::SlackApiError(ref e)) if e == "missing_scope"));
+    }
+
+    #[test]
+    fn test_format_conversations_text_rows() {
+        let client = MockListClient::new(vec![PAGE_1, PAGE_2]);
+        let conversations =
+            list_all_conversations(&client, "xoxb-test", DEFAULT_CONVERSATION_TYPES).unwrap();
+        let output = format_conversations(&conversations, OutputFormat::Text).unwrap();
+        assert_eq!(
+            output,
+            "C111\t#general\tpublic\nC222\t#random\tpublic\nG333\t#ops-private\tprivate\n"
+        );
+    }
+
+    #[test]
+    fn test_format_conversations_unnamed() {
+        let conversations = vec![Conversation {
+            id: "D444".to_string(),
+            name: None,
+            is_private: true,
+        }];
+        let output = format_conversations(&conversations, OutputFormat::Text).unwrap();
+        assert_eq!(output, "D444\t\tprivate\n");
+    }
+
+    #[test]
+    fn test_format_conversations_json() {
+        let client = MockListClient::new(vec![PAGE_1, PAGE_2]);
+        let conversations =
+            list_all_conversations(&client, "xoxb-test", DEFAULT_CONVERSATION_TYPES).unwrap();
+        let output = format_conversations(&conversations, OutputFormat::Json).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 3);
+        assert_eq!(
+            json[2],
+            serde_json::json!({"id": "G333", "name": "ops-private", "is_private": true})
+        );
+    }
+
+    #[test]
+    fn test_decorate_channel_suffix() {
+        assert_eq!(
+            decorate_channel("alerts", None, Some("-prod")),
+            "#alerts-prod"
+        );
+        assert_eq!(
+            decorate_channel("#alerts", None, Some("-prod")),
+            "#alerts-prod"
+        );
+    }
+
+    #[test]
+    fn test_decorate_channel_skips_ids_and_users() {
+        assert_eq!(
+            decorate_channel("C0123456789", None, Some("-prod")),
+            "C0123456789"
+        );
+        assert_eq!(
+            decorate_channel("@jdoe", Some("team-"), Some("-prod")),
+            "@jdoe"
+        );
+    }
+
+    #[test]
+    fn test_decorate_channel_prefix_and_suffix() {
+        assert_eq!(
+            decorate_channel("#alerts", Some("payments-"), Some("-staging")),
+            "#payments-alerts-staging"
+        );
+        assert_eq!(
+            decorate_channel("alerts", Some("payments-"), None),
+            "#payments-alerts"
+        );
+    }
+
+    #[test]
+    fn test_decorate_channel_without_affixes_is_unchanged() {
+        assert_eq!(decorate_channel("alerts", None, None), "alerts");
+    }
+
+    #[test]
+    fn test_resolve_name_to_id() {
+        let client = MockListClient::new(vec![PAGE_1, PAGE_2]);
+        let resolver = ChannelResolver::default();
+        assert_eq!(
+            resolver
+                .resolve(&client, "xoxb-test", "#ops-private")
+                .unwrap(),
+            "G333"
+        );
+    }
+
+    #[test]
+    fn test_resolve_uses_cache() {
+        let client = MockListClient::new(vec![PAGE_1, PAGE_2]);
+        let resolver = ChannelResolver::default();
+        resolver.resolve(&client, "xoxb-test", "#general").unwrap();
+        assert_eq!(
+            resolver.resolve(&client, "xoxb-test", "#random").unwrap(),
+            "C222"
+        );
+        assert_eq!(client.calls.get(), 2);
+    }
+
+    #[test]
+    fn test_main_and_also_channels_resolve_from_one_listing() {
+        // As a run does: the --channel and every --also-channel share one resolver
+        let client = MockListClient::new(vec![PAGE_1, PAGE_2]);
+        let resolver = ChannelResolver::default();
+        let resolved: Vec<String> = ["#general", "#ops-private", "#random"]
+            .iter()
+            .map(|channel| resolver.resolve(&client, "xoxb-test", channel).unwrap())
+            .collect();
+        assert_eq!(resolved, ["C111", "G333", "C222"]);
+        // Both pages of a single conversations.list pass, and no more
+        assert_eq!(client.calls.get(), 2);
+    }
+
+    #[test]
+    fn test_resolve_id_passthrough() {
+        let client = MockListClient::new(vec![]);
+        let resolver = ChannelResolver::default();
+        assert_eq!(
+            resolver
+                .resolve(&client, "xoxb-test", "C0123456789")
+                .unwrap(),
+            "C0123456789"
+        );
+        assert_eq!(client.calls.get(), 0);
+    }
+
+    #[test]
+    fn test_resolve_unknown_name() {
+        let client = MockListClient::new(vec![PAGE_1, PAGE_2]);
+        let resolver = ChannelResolver::default();
+        let result = resolver.resolve(&client, "xoxb-test", "#nope");
+        assert!(matches!(result, Err(SlackCliError::ChannelNotFound(ref c)) if c == "#nope"));
+    }
+}
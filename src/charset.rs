@@ -0,0 +1,107 @@
+use crate::SlackCliError;
+use encoding_rs::Encoding;
+
+/// What to do with bytes that aren't valid in the input charset
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum OnInvalid {
+    /// Substitute U+FFFD for each malformed sequence
+    Replace,
+    /// Refuse the input
+    #[default]
+    Error,
+}
+
+impl std::str::FromStr for OnInvalid {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "replace" => Ok(OnInvalid::Replace),
+            "error" => Ok(OnInvalid::Error),
+            _ => Err(format!(
+                "unknown --on-invalid value '{s}' (expected replace or error)"
+            )),
+        }
+    }
+}
+
+/// Decodes message input to UTF-8. `charset` is a WHATWG label such as
+/// `windows-1252` or `latin1`; without one the input must already be UTF-8.
+/// A leading byte order mark is not treated specially.
+pub fn decode_input(
+    bytes: &[u8],
+    charset: Option<&str>,
+    on_invalid: OnInvalid,
+) -> Result<String, SlackCliError> {
+    let encoding = match charset {
+        Some(label) => Encoding::for_label(label.trim().as_bytes()).ok_or_else(|| {
+            SlackCliError::InvalidConfig(format!("unknown input encoding '{label}'"))
+        })?,
+        None => encoding_rs::UTF_8,
+    };
+
+    match on_invalid {
+        OnInvalid::Replace => Ok(encoding.decode_without_bom_handling(bytes).0.into_owned()),
+        OnInvalid::Error => encoding
+            .decode_without_bom_handling_and_without_replacement(bytes)
+            .map(|text| text.into_owned())
+            .ok_or_else(|| {
+                SlackCliError::InvalidInput(format!(
+                    "input is not valid {} (pass --input-encoding, or --on-invalid replace)",
+                    encoding.name()
+                ))
+            }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_windows_1252_transcoded() {
+        // "Café – 5 €" as Windows-1252
+        let bytes = b"Caf\xe9 \x96 5 \x80";
+        let text = decode_input(bytes, Some("windows-1252"), OnInvalid::Error).unwrap();
+        assert_eq!(text, "Café – 5 €");
+    }
+
+    #[test]
+    fn test_latin1_label_accepted() {
+        let text = decode_input(b"na\xefve", Some("latin1"), OnInvalid::Error).unwrap();
+        assert_eq!(text, "naïve");
+    }
+
+    #[test]
+    fn test_default_is_strict_utf8() {
+        assert_eq!(
+            decode_input("déjà vu".as_bytes(), None, OnInvalid::Error).unwrap(),
+            "déjà vu"
+        );
+        assert!(matches!(
+            decode_input(b"Caf\xe9", None, OnInvalid::Error),
+            Err(SlackCliError::InvalidInput(ref msg)) if msg.contains("UTF-8")
+        ));
+    }
+
+    #[test]
+    fn test_replace_invalid_sequences() {
+        let text = decode_input(b"Caf\xe9!", None, OnInvalid::Replace).unwrap();
+        assert_eq!(text, "Caf\u{fffd}!");
+    }
+
+    #[test]
+    fn test_unknown_charset_rejected() {
+        assert!(matches!(
+            decode_input(b"x", Some("klingon"), OnInvalid::Error),
+            Err(SlackCliError::InvalidConfig(ref msg)) if msg.contains("klingon")
+        ));
+    }
+
+    #[test]
+    fn test_on_invalid_from_str() {
+        assert_eq!("replace".parse::<OnInvalid>(), Ok(OnInvalid::Replace));
+        assert_eq!("ERROR".parse::<OnInvalid>(), Ok(OnInvalid::Error));
+        assert!("skip".parse::<OnInvalid>().is_err());
+    }
+}
@@ -0,0 +1,333 @@
+/// Color keywords accepted by `--color`, with the hex Slack renders for each
+pub const COLOR_KEYWORDS: &[(&str, &str)] = &[
+    ("good", "#36a64f"),
+    ("success", "#36a64f"),
+    ("warning", "#daa038"),
+    ("danger", "#a30200"),
+    ("error", "#a30200"),
+];
+
+/// CSS named colors (CSS Color Module Level 4), also accepted by `--color`.
+/// The keywords above win where a name appears in both.
+pub const CSS_COLORS: &[(&str, &str)] = &[
+    ("aliceblue", "#f0f8ff"),
+    ("antiquewhite", "#faebd7"),
+    ("aqua", "#00ffff"),
+    ("aquamarine", "#7fffd4"),
+    ("azure", "#f0ffff"),
+    ("beige", "#f5f5dc"),
+    ("bisque", "#ffe4c4"),
+    ("black", "#000000"),
+    ("blanchedalmond", "#ffebcd"),
+    ("blue", "#0000ff"),
+    ("blueviolet", "#8a2be2"),
+    ("brown", "#a52a2a"),
+    ("burlywood", "#deb887"),
+    ("cadetblue", "#5f9ea0"),
+    ("chartreuse", "#7fff00"),
+    ("chocolate", "#d2691e"),
+    ("coral", "#ff7f50"),
+    ("cornflowerblue", "#6495ed"),
+    ("cornsilk", "#fff8dc"),
+    ("crimson", "#dc143c"),
+    ("cyan", "#00ffff"),
+    ("darkblue", "#00008b"),
+    ("darkcyan", "#008b8b"),
+    ("darkgoldenrod", "#b8860b"),
+    ("darkgray", "#a9a9a9"),
+    ("darkgreen", "#006400"),
+    ("darkgrey", "#a9a9a9"),
+    ("darkkhaki", "#bdb76b"),
+    ("darkmagenta", "#8b008b"),
+    ("darkolivegreen", "#556b2f"),
+    ("darkorange", "#ff8c00"),
+    ("darkorchid", "#9932cc"),
+    ("darkred", "#8b0000"),
+    ("darksalmon", "#e9967a"),
+    ("darkseagreen", "#8fbc8f"),
+    ("darkslateblue", "#483d8b"),
+    ("darkslategray", "#2f4f4f"),
+    ("darkslategrey", "#2f4f4f"),
+    ("darkturquoise", "#00ced1"),
+    ("darkviolet", "#9400d3"),
+    ("deeppink", "#ff1493"),
+    ("deepskyblue", "#00bfff"),
+    ("dimgray", "#696969"),
+    ("dimgrey", "#696969"),
+    ("dodgerblue", "#1e90ff"),
+    ("firebrick", "#b22222"),
+    ("floralwhite", "#fffaf0"),
+    ("forestgreen", "#228b22"),
+    ("fuchsia", "#ff00ff"),
+    ("gainsboro", "#dcdcdc"),
+    ("ghostwhite", "#f8f8ff"),
+    ("gold", "#ffd700"),
+    ("goldenrod", "#daa520"),
+    ("gray", "#808080"),
+    ("green", "#008000"),
+    ("greenyellow", "#adff2f"),
+    ("grey", "#808080"),
+    ("honeydew", "#f0fff0"),
+    ("hotpink", "#ff69b4"),
+    ("indianred", "#cd5c5c"),
+    ("indigo", "#4b0082"),
+    ("ivory", "#fffff0"),
+    ("khaki", "#f0e68c"),
+    ("lavender", "#e6e6fa"),
+    ("lavenderblush", "#fff0f5"),
+    ("lawngreen", "#7cfc00"),
+    ("lemonchiffon", "#fffacd"),
+    ("lightblue", "#add8e6"),
+    ("lightcoral", "#f08080"),
+    ("lightcyan", "#e0ffff"),
+    ("lightgoldenrodyellow", "#fafad2"),
+    ("lightgray", "#d3d3d3"),
+    ("lightgreen", "#90ee90"),
+    ("lightgrey", "#d3d3d3"),
+    ("lightpink", "#ffb6c1"),
+    ("lightsalmon", "#ffa07a"),
+    ("lightseagreen", "#20b2aa"),
+    ("lightskyblue", "#87cefa"),
+    ("lightslategray", "#778899"),
+    ("lightslategrey", "#778899"),
+    ("lightsteelblue", "#b0c4de"),
+    ("lightyellow", "#ffffe0"),
+    ("lime", "#00ff00"),
+    ("limegreen", "#32cd32"),
+    ("linen", "#faf0e6"),
+    ("magenta", "#ff00ff"),
+    ("maroon", "#800000"),
+    ("mediumaquamarine", "#66cdaa"),
+    ("mediumblue", "#0000cd"),
+    ("mediumorchid", "#ba55d3"),
+    ("mediumpurple", "#9370db"),
+    ("mediumseagreen", "#3cb371"),
+    ("mediumslateblue", "#7b68ee"),
+    ("mediumspringgreen", "#00fa9a"),
+    ("mediumturquoise", "#48d1cc"),
+    ("mediumvioletred", "#c71585"),
+    ("midnightblue", "#191970"),
+    ("mintcream", "#f5fffa"),
+    ("mistyrose", "#ffe4e1"),
+    ("moccasin", "#ffe4b5"),
+    ("navajowhite", "#ffdead"),
+    ("navy", "#000080"),
+    ("oldlace", "#fdf5e6"),
+    ("olive", "#808000"),
+    ("olivedrab", "#6b8e23"),
+    ("orange", "#ffa500"),
+    ("orangered", "#ff4500"),
+    ("orchid", "#da70d6"),
+    ("palegoldenrod", "#eee8aa"),
+    ("palegreen", "#98fb98"),
+    ("paleturquoise", "#afeeee"),
+    ("palevioletred", "#db7093"),
+    ("papayawhip", "#ffefd5"),
+    ("peachpuff", "#ffdab9"),
+    ("peru", "#cd853f"),
+    ("pink", "#ffc0cb"),
+    ("plum", "#dda0dd"),
+    ("powderblue", "#b0e0e6"),
+    ("purple", "#800080"),
+    ("rebeccapurple", "#663399"),
+    ("red", "#ff0000"),
+    ("rosybrown", "#bc8f8f"),
+    ("royalblue", "#4169e1"),
+    ("saddlebrown", "#8b4513"),
+    ("salmon", "#fa8072"),
+    ("sandybrown", "#f4a460"),
+    ("seagreen", "#2e8b57"),
+    ("seashell", "#fff5ee"),
+    ("sienna", "#a0522d"),
+    ("silver", "#c0c0c0"),
+    ("skyblue", "#87ceeb"),
+    ("slateblue", "#6a5acd"),
+    ("slategray", "#708090"),
+    ("slategrey", "#708090"),
+    ("snow", "#fffafa"),
+    ("springgreen", "#00ff7f"),
+    ("steelblue", "#4682b4"),
+    ("tan", "#d2b48c"),
+    ("teal", "#008080"),
+    ("thistle", "#d8bfd8"),
+    ("tomato", "#ff6347"),
+    ("turquoise", "#40e0d0"),
+    ("violet", "#ee82ee"),
+    ("wheat", "#f5deb3"),
+    ("white", "#ffffff"),
+    ("whitesmoke", "#f5f5f5"),
+    ("yellow", "#ffff00"),
+    ("yellowgreen", "#9acd32"),
+];
+
+/// Distinct, readable sidebar colors that `--color-by` picks from. Appending is
+/// fine, but reordering or removing entries changes every source's color.
+pub const SOURCE_PALETTE: &[&str] = &[
+    "#1f77b4", // blue
+    "#ff7f0e", // orange
+    "#2ca02c", // green
+    "#d62728", // red
+    "#9467bd", // purple
+    "#8c564b", // brown
+    "#e377c2", // pink
+    "#7f7f7f", // gray
+    "#bcbd22", // olive
+    "#17becf", // cyan
+    "#393b79", // indigo
+    "#ad494a", // brick
+];
+
+/// The palette color for `source`, the same on every run and platform
+pub fn color_for_source(source: &str) -> &'static str {
+    let hash = crate::state::fnv1a_64(source.as_bytes());
+    SOURCE_PALETTE[(hash % SOURCE_PALETTE.len() as u64) as usize]
+}
+
+/// `--color` value that picks the color from the message's log level
+pub const AUTO_COLOR: &str = "auto";
+
+/// Log level tokens `--color auto` looks for, with the keyword each maps to.
+/// DEBUG is recognized but left uncolored.
+const LOG_LEVELS: &[(&str, Option<&str>)] = &[
+    ("ERROR", Some("danger")),
+    ("WARNING", Some("warning")),
+    ("WARN", Some("warning")),
+    ("INFO", Some("good")),
+    ("DEBUG", None),
+];
+
+/// The keyword color for the first log level token (e.g. `ERROR`, `[WARN]`,
+/// `level=INFO`) on the first line of `text`, or `None` when there is none
+pub fn log_level_color(text: &str) -> Option<&'static str> {
+    let first_line = text.lines().next()?;
+    first_line
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .find_map(|token| LOG_LEVELS.iter().find(|(level, _)| *level == token))
+        .and_then(|(_, color)| *color)
+}
+
+/// Parses "#RRGGBB" (case-insensitive) into its components
+pub fn hex_to_rgb(hex: &str) -> Option<(u8, u8, u8)> {
+    let digits = hex.strip_prefix('#')?;
+    if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// A two-cell truecolor background block showing `hex`, or `None` if it isn't "#RRGGBB"
+pub fn swatch(hex: &str) -> Option<String> {
+    let (r, g, b) = hex_to_rgb(hex)?;
+    Some(format!("\x1b[48;2;{r};{g};{b}m  \x1b[0m"))
+}
+
+/// Swatches are only drawn on a terminal, and never when `--no-color-output`
+/// is given or `NO_COLOR` is set to a non-empty value (https://no-color.org).
+pub fn use_swatches(is_terminal: bool, no_color_flag: bool, no_color_env: Option<&str>) -> bool {
+    is_terminal && !no_color_flag && no_color_env.is_none_or(str::is_empty)
+}
+
+/// The `--list-colors` table: one "keyword<TAB>#hex" row per keyword, with a swatch when enabled
+pub fn format_color_list(swatches: bool) -> String {
+    COLOR_KEYWORDS
+        .iter()
+        .map(|(name, hex)| match swatch(hex).filter(|_| swatches) {
+            Some(block) => format!("{name}\t{hex}\t{block}\n"),
+            None => format!("{name}\t{hex}\n"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_to_rgb() {
+        assert_eq!(hex_to_rgb("#36a64f"), Some((0x36, 0xa6, 0x4f)));
+        assert_eq!(hex_to_rgb("#FFFFFF"), Some((255, 255, 255)));
+        assert_eq!(hex_to_rgb("#000000"), Some((0, 0, 0)));
+    }
+
+    #[test]
+    fn test_css_colors_are_valid_hex() {
+        for (name, hex) in CSS_COLORS {
+            assert_eq!(name.to_lowercase(), *name);
+            assert!(hex_to_rgb(hex).is_some(), "{name}: {hex}");
+            assert_eq!(hex.to_lowercase(), *hex);
+        }
+    }
+
+    #[test]
+    fn test_log_level_color() {
+        assert_eq!(
+            log_level_color("2024-01-15T10:30:00Z ERROR db: connection refused"),
+            Some("danger")
+        );
+        assert_eq!(log_level_color("[WARN] disk 91% full"), Some("warning"));
+        assert_eq!(log_level_color("level=WARNING msg=slow"), Some("warning"));
+        assert_eq!(log_level_color("INFO deploy finished"), Some("good"));
+        assert_eq!(log_level_color("DEBUG retrying after ERROR"), None);
+        // Only the first line counts, and only upper-case tokens
+        assert_eq!(log_level_color("deploy finished\nERROR later"), None);
+        assert_eq!(log_level_color("no error here"), None);
+        assert_eq!(log_level_color("ERRORS: 0"), None);
+        assert_eq!(log_level_color(""), None);
+    }
+
+    #[test]
+    fn test_hex_to_rgb_rejects_malformed() {
+        assert_eq!(hex_to_rgb("36a64f"), None);
+        assert_eq!(hex_to_rgb("#36a64"), None);
+        assert_eq!(hex_to_rgb("#36a64g"), None);
+        assert_eq!(hex_to_rgb("#é6a64f"), None);
+    }
+
+    #[test]
+    fn test_swatch_escape_sequence() {
+        assert_eq!(swatch("#a30200").unwrap(), "\x1b[48;2;163;2;0m  \x1b[0m");
+        assert_eq!(swatch("danger"), None);
+    }
+
+    #[test]
+    fn test_use_swatches_escapes() {
+        assert!(use_swatches(true, false, None));
+        assert!(use_swatches(true, false, Some("")));
+        assert!(!use_swatches(false, false, None));
+        assert!(!use_swatches(true, true, None));
+        assert!(!use_swatches(true, false, Some("1")));
+    }
+
+    #[test]
+    fn test_color_for_source_is_stable() {
+        assert_eq!(color_for_source("api"), color_for_source("api"));
+        // Pinned so a change to the hash or palette order, which would recolor
+        // every existing source, is noticed
+        assert_eq!(color_for_source("api"), "#d62728");
+        assert_eq!(color_for_source("db"), "#ad494a");
+        assert_eq!(color_for_source("frontend"), "#ff7f0e");
+    }
+
+    #[test]
+    fn test_color_for_source_spreads_inputs() {
+        let sources = [
+            "api", "db", "cache", "worker", "frontend", "billing", "auth", "search",
+        ];
+        let colors: std::collections::HashSet<&str> =
+            sources.iter().map(|s| color_for_source(s)).collect();
+        assert!(colors.len() >= 5, "only {} distinct colors", colors.len());
+        assert!(SOURCE_PALETTE.iter().all(|c| hex_to_rgb(c).is_some()));
+    }
+
+    #[test]
+    fn test_format_color_list() {
+        let plain = format_color_list(false);
+        assert!(plain.starts_with("good\t#36a64f\n"));
+        assert_eq!(plain.lines().count(), COLOR_KEYWORDS.len());
+        assert!(!plain.contains('\x1b'));
+
+        let colored = format_color_list(true);
+        assert!(colored.starts_with("good\t#36a64f\t\x1b[48;2;54;166;79m  \x1b[0m\n"));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::SlackCliError;
+use std::process::{Command, ExitStatus};
+
+/// Longest command shown in the title before it is cut with "…"
+const TITLE_COMMAND_MAX: usize = 100;
+
+/// What `--run` captured from a command
+pub struct CommandReport {
+    pub command: String,
+    pub status: ExitStatus,
+    /// stdout and stderr, interleaved as the command wrote them
+    pub output: String,
+}
+
+/// Runs `command` through `sh -c` with stderr merged into stdout.
+pub fn run_command(command: &str) -> Result<CommandReport, SlackCliError> {
+    // The group keeps `2>&1` applying to the whole command line, pipelines included
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{{ {command}\n}} 2>&1"))
+        .output()
+        .map_err(|e| SlackCliError::CommandError(format!("failed to run `{command}`: {e}")))?;
+
+    Ok(CommandReport {
+        command: command.to_string(),
+        status: output.status,
+        output: String::from_utf8_lossy(&output.stdout).into_owned(),
+    })
+}
+
+impl CommandReport {
+    pub fn success(&self) -> bool {
+        self.status.success()
+    }
+
+    fn status_text(&self) -> String {
+        match self.status.code() {
+            Some(code) => format!("exit {code}"),
+            None => "killed by a signal".to_string(),
+        }
+    }
+
+    /// `$ make deploy (exit 2)`, with long commands shortened
+    pub fn title(&self) -> String {
+        let command = self.command.trim();
+        let command = if command.chars().count() > TITLE_COMMAND_MAX {
+            let cut: String = command.chars().take(TITLE_COMMAND_MAX - 1).collect();
+            format!("{cut}…")
+        } else {
+            command.to_string()
+        };
+        format!("$ {command} ({})", self.status_text())
+    }
+
+    /// The exit status line followed by the output, optionally as a code block
+    pub fn message(&self, code_block: bool) -> String {
+        let output = self.output.trim_end();
+        let status = match self.status.code() {
+            Some(code) => format!("Exit status: {code}"),
+            None => "Killed by a signal".to_string(),
+        };
+        if output.is_empty() {
+            format!("{status} (no output)")
+        } else if code_block {
+            format!("{status}\n```\n{output}\n```")
+        } else {
+            format!("{status}\n{output}")
+        }
+    }
+
+    /// `good` when the command succeeded, `danger` otherwise
+    pub fn color(&self) -> &'static str {
+        if self.success() {
+            "good"
+        } else {
+            "danger"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_succeeding_command() {
+        let report = run_command("echo hello; echo oops >&2").unwrap();
+        assert!(report.success());
+        assert_eq!(report.output, "hello\noops\n");
+        assert_eq!(report.title(), "$ echo hello; echo oops >&2 (exit 0)");
+        assert_eq!(report.message(false), "Exit status: 0\nhello\noops");
+        assert_eq!(report.color(), "good");
+    }
+
+    #[test]
+    fn test_failing_command() {
+        let report = run_command("echo 'disk full' >&2; exit 3").unwrap();
+        assert!(!report.success());
+        assert_eq!(report.title(), "$ echo 'disk full' >&2; exit 3 (exit 3)");
+        assert_eq!(report.message(true), "Exit status: 3\n```\ndisk full\n```");
+        assert_eq!(report.color(), "danger");
+    }
+
+    #[test]
+    fn test_command_without_output() {
+        let report = run_command("true").unwrap();
+        assert_eq!(report.message(true), "Exit status: 0 (no output)");
+    }
+
+    #[test]
+    fn test_long_command_title_is_shortened() {
+        let command = format!("echo {}", "x".repeat(200));
+        let report = run_command(&command).unwrap();
+        let title = report.title();
+        assert!(title.starts_with("$ echo xxx"));
+        assert!(title.ends_with("x… (exit 0)"));
+        assert_eq!(
+            title.chars().count(),
+            "$ ".len() + TITLE_COMMAND_MAX + " (exit 0)".len()
+        );
+    }
+}
@@ -0,0 +1,439 @@
+use crate::host::HostnameSource;
+use crate::{template, SlackCliError};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+
+pub const UNFURL_LINKS_ENV: &str = "SLACK_CLI_UNFURL_LINKS";
+pub const UNFURL_MEDIA_ENV: &str = "SLACK_CLI_UNFURL_MEDIA";
+pub const JITTER_SEED_ENV: &str = "SLACK_CLI_JITTER_SEED";
+pub const TIMEOUT_ENV: &str = "SLACK_CLI_TIMEOUT";
+pub const API_BASE_ENV: &str = "SLACK_API_BASE";
+
+/// Per-user defaults applied to every send unless a CLI flag overrides them
+#[derive(Default, Debug, PartialEq)]
+pub struct Defaults {
+    pub unfurl_links: Option<bool>,
+    pub unfurl_media: Option<bool>,
+    /// Fixed retry jitter seed, for reproducible delays when debugging
+    pub jitter_seed: Option<u64>,
+    /// HTTP request timeout in seconds; 0 disables it
+    pub timeout_secs: Option<u64>,
+    /// Web API root to use instead of https://slack.com/api, e.g. an internal gateway
+    pub api_base: Option<String>,
+}
+
+impl Defaults {
+    pub fn from_env() -> Result<Self, SlackCliError> {
+        Defaults::from_lookup(|key| env::var(key).ok())
+    }
+
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, SlackCliError> {
+        let bool_var = |key: &str| {
+            lookup(key)
+                .filter(|v| !v.trim().is_empty())
+                .map(|v| parse_bool(key, &v))
+                .transpose()
+        };
+
+        let u64_var = |key: &str| {
+            lookup(key)
+                .filter(|v| !v.trim().is_empty())
+                .map(|v| {
+                    v.trim().parse::<u64>().map_err(|_| {
+                        SlackCliError::InvalidConfig(format!(
+                            "{key}: expected an unsigned integer, got '{v}'"
+                        ))
+                    })
+                })
+                .transpose()
+        };
+
+        Ok(Defaults {
+            unfurl_links: bool_var(UNFURL_LINKS_ENV)?,
+            unfurl_media: bool_var(UNFURL_MEDIA_ENV)?,
+            jitter_seed: u64_var(JITTER_SEED_ENV)?,
+            timeout_secs: u64_var(TIMEOUT_ENV)?,
+            api_base: lookup(API_BASE_ENV)
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty()),
+        })
+    }
+
+    /// Merges the `--unfurl-links` / `--unfurl-media` flags over the configured defaults.
+    /// A flag re-enables unfurling for this message; without it the default applies.
+    pub fn unfurl(&self, links_flag: bool, media_flag: bool) -> (Option<bool>, Option<bool>) {
+        let merge = |flag: bool, default: Option<bool>| if flag { Some(true) } else { default };
+        (
+            merge(links_flag, self.unfurl_links),
+            merge(media_flag, self.unfurl_media),
+        )
+    }
+}
+
+fn parse_bool(key: &str, value: &str) -> Result<bool, SlackCliError> {
+    match value.trim().to_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" => Ok(false),
+        _ => Err(SlackCliError::InvalidConfig(format!(
+            "{key}: expected true or false, got '{value}'"
+        ))),
+    }
+}
+
+/// Settings that can be given globally or per channel in the config file
+#[derive(Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelDefaults {
+    pub color: Option<String>,
+    /// May use `{{hostname}}` and `{{env:VAR}}`; see [`ChannelDefaults::rendered_username`]
+    pub username: Option<String>,
+}
+
+impl ChannelDefaults {
+    /// The configured username with its template tokens expanded
+    pub fn rendered_username(
+        &self,
+        hostname: &dyn HostnameSource,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Option<String>, SlackCliError> {
+        self.username
+            .as_deref()
+            .map(|username| template::render(username, hostname, env))
+            .transpose()
+    }
+}
+
+/// The TOML config file, `~/.config/slack-cli/config.toml` unless `--config` says otherwise:
+///
+/// ```toml
+/// channel = "#deploys"
+/// color = "good"
+///
+/// [channels."#alerts"]
+/// color = "danger"
+/// username = "Alerter"
+/// ```
+#[derive(Deserialize, Default, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CliConfig {
+    /// Channel to post to when no `--channel` is given
+    pub channel: Option<String>,
+    /// Title used when neither `--title` nor `--run` gives one
+    pub title: Option<String>,
+    /// HTTP request timeout in seconds, below `--timeout` and `SLACK_CLI_TIMEOUT`
+    pub timeout: Option<u64>,
+    /// Token file read instead of `~/.slack/api-token` and `/etc/slack/api-token`
+    pub token_file: Option<PathBuf>,
+    #[serde(flatten)]
+    pub global: ChannelDefaults,
+    #[serde(default)]
+    pub channels: HashMap<String, ChannelDefaults>,
+}
+
+impl CliConfig {
+    pub fn parse(contents: &str) -> Result<Self, SlackCliError> {
+        toml::from_str(contents).map_err(|e| SlackCliError::InvalidConfig(e.to_string()))
+    }
+
+    /// Loads an explicitly requested config file, which must exist.
+    pub fn load(path: &Path) -> Result<Self, SlackCliError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            SlackCliError::InvalidConfig(format!("failed to read {}: {e}", path.display()))
+        })?;
+        CliConfig::parse(&contents).map_err(|e| match e {
+            SlackCliError::InvalidConfig(msg) => {
+                SlackCliError::InvalidConfig(format!("{}: {msg}", path.display()))
+            }
+            other => other,
+        })
+    }
+
+    /// Loads the default config file if there is one.
+    pub fn load_default() -> Result<Self, SlackCliError> {
+        match default_config_path() {
+            Some(path) if path.is_file() => CliConfig::load(&path),
+            _ => Ok(CliConfig::default()),
+        }
+    }
+
+    /// `token_file` with a leading `~/` expanded to `home`
+    pub fn token_file_in(&self, home: &str) -> Option<PathBuf> {
+        let path = self.token_file.as_ref()?;
+        Some(match path.strip_prefix("~") {
+            Ok(rest) => Path::new(home).join(rest),
+            Err(_) => path.clone(),
+        })
+    }
+
+    /// Settings for `channel`: a matching `[channels]` entry wins over the global values.
+    /// `#name` and `name` match each other; IDs match exactly.
+    pub fn for_channel(&self, channel: &str) -> ChannelDefaults {
+        let wanted = channel.strip_prefix('#').unwrap_or(channel);
+        let entry = self
+            .channels
+            .iter()
+            .find(|(key, _)| key.strip_prefix('#').unwrap_or(key) == wanted)
+            .map(|(_, entry)| entry);
+
+        ChannelDefaults {
+            color: entry
+                .and_then(|e| e.color.clone())
+                .or_else(|| self.global.color.clone()),
+            username: entry
+                .and_then(|e| e.username.clone())
+                .or_else(|| self.global.username.clone()),
+        }
+    }
+}
+
+/// `$XDG_CONFIG_HOME/slack-cli/config.toml`, falling back to `~/.config/slack-cli/config.toml`
+pub fn default_config_path() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("slack-cli").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host::FixedHostname;
+    use std::collections::HashMap;
+
+    fn defaults_from(vars: &[(&str, &str)]) -> Result<Defaults, SlackCliError> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        Defaults::from_lookup(|key| vars.get(key).cloned())
+    }
+
+    #[test]
+    fn test_no_env_leaves_unfurl_unset() {
+        let defaults = defaults_from(&[]).unwrap();
+        assert_eq!(defaults, Defaults::default());
+        assert_eq!(defaults.unfurl(false, false), (None, None));
+    }
+
+    #[test]
+    fn test_env_default_applied() {
+        let defaults =
+            defaults_from(&[(UNFURL_LINKS_ENV, "false"), (UNFURL_MEDIA_ENV, "0")]).unwrap();
+        assert_eq!(defaults.unfurl(false, false), (Some(false), Some(false)));
+    }
+
+    #[test]
+    fn test_cli_flag_overrides_default() {
+        let defaults =
+            defaults_from(&[(UNFURL_LINKS_ENV, "false"), (UNFURL_MEDIA_ENV, "false")]).unwrap();
+        assert_eq!(defaults.unfurl(true, false), (Some(true), Some(false)));
+        assert_eq!(defaults.unfurl(false, true), (Some(false), Some(true)));
+    }
+
+    #[test]
+    fn test_invalid_env_value_rejected() {
+        let result = defaults_from(&[(UNFURL_LINKS_ENV, "maybe")]);
+        assert!(matches!(
+            result,
+            Err(SlackCliError::InvalidConfig(ref msg)) if msg.contains(UNFURL_LINKS_ENV)
+        ));
+    }
+
+    #[test]
+    fn test_jitter_seed_from_env() {
+        let defaults = defaults_from(&[(JITTER_SEED_ENV, " 1234 ")]).unwrap();
+        assert_eq!(defaults.jitter_seed, Some(1234));
+    }
+
+    #[test]
+    fn test_invalid_jitter_seed_rejected() {
+        let result = defaults_from(&[(JITTER_SEED_ENV, "abc")]);
+        assert!(
+            matches!(result, Err(SlackCliError::InvalidConfig(ref msg)) if msg.contains(JITTER_SEED_ENV))
+        );
+    }
+
+    #[test]
+    fn test_timeout_from_env() {
+        let defaults = defaults_from(&[(TIMEOUT_ENV, "90")]).unwrap();
+        assert_eq!(defaults.timeout_secs, Some(90));
+        let defaults = defaults_from(&[(TIMEOUT_ENV, "0")]).unwrap();
+        assert_eq!(defaults.timeout_secs, Some(0));
+        assert_eq!(defaults_from(&[]).unwrap().timeout_secs, None);
+    }
+
+    #[test]
+    fn test_api_base_from_env() {
+        let defaults = defaults_from(&[(API_BASE_ENV, " http://localhost:9000/api ")]).unwrap();
+        assert_eq!(
+            defaults.api_base.as_deref(),
+            Some("http://localhost:9000/api")
+        );
+        assert_eq!(
+            defaults_from(&[(API_BASE_ENV, "  ")]).unwrap().api_base,
+            None
+        );
+    }
+
+    #[test]
+    fn test_invalid_timeout_rejected() {
+        for value in ["-5", "30s", "1.5"] {
+            let result = defaults_from(&[(TIMEOUT_ENV, value)]);
+            assert!(
+                matches!(result, Err(SlackCliError::InvalidConfig(ref msg)) if msg.contains(TIMEOUT_ENV)),
+                "{value}"
+            );
+        }
+    }
+
+    const CONFIG: &str = r##"
+color = "good"
+username = "Deploy Bot"
+
+[channels."#alerts"]
+color = "danger"
+username = "Alerter"
+
+[channels.C0123456789]
+username = "By ID"
+"##;
+
+    #[test]
+    fn test_channel_entry_overrides_global() {
+        let config = CliConfig::parse(CONFIG).unwrap();
+        let alerts = config.for_channel("#alerts");
+        assert_eq!(alerts.color.as_deref(), Some("danger"));
+        assert_eq!(alerts.username.as_deref(), Some("Alerter"));
+        assert_eq!(config.for_channel("alerts"), alerts);
+    }
+
+    #[test]
+    fn test_unmatched_channel_uses_global() {
+        let config = CliConfig::parse(CONFIG).unwrap();
+        let general = config.for_channel("#general");
+        assert_eq!(general.color.as_deref(), Some("good"));
+        assert_eq!(general.username.as_deref(), Some("Deploy Bot"));
+    }
+
+    #[test]
+    fn test_partial_channel_entry_falls_back_per_field() {
+        let config = CliConfig::parse(CONFIG).unwrap();
+        let by_id = config.for_channel("C0123456789");
+        assert_eq!(by_id.username.as_deref(), Some("By ID"));
+        assert_eq!(by_id.color.as_deref(), Some("good"));
+    }
+
+    #[test]
+    fn test_inline_channel_map() {
+        let config = CliConfig::parse(
+            r##"channels = { "#alerts" = { color = "danger", username = "Alerter" } }"##,
+        )
+        .unwrap();
+        assert_eq!(
+            config.for_channel("#alerts").color.as_deref(),
+            Some("danger")
+        );
+        assert_eq!(config.for_channel("#other"), ChannelDefaults::default());
+    }
+
+    #[test]
+    fn test_templated_default_username() {
+        let config = CliConfig::parse(
+            r##"
+username = "deploy@{{hostname}} ({{env:DEPLOY_ENV}})"
+
+[channels."#alerts"]
+username = "Alerter"
+"##,
+        )
+        .unwrap();
+        let env = |key: &str| (key == "DEPLOY_ENV").then(|| "prod".to_string());
+
+        let general = config.for_channel("#general");
+        assert_eq!(
+            general
+                .rendered_username(&FixedHostname(Some("web01")), env)
+                .unwrap()
+                .as_deref(),
+            Some("deploy@web01 (prod)")
+        );
+        let alerts = config.for_channel("#alerts");
+        assert_eq!(
+            alerts
+                .rendered_username(&FixedHostname(Some("web01")), env)
+                .unwrap()
+                .as_deref(),
+            Some("Alerter")
+        );
+        assert_eq!(
+            ChannelDefaults::default()
+                .rendered_username(&FixedHostname(Some("web01")), env)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_templated_username_with_unset_env_is_error() {
+        let config = CliConfig::parse(r#"username = "bot ({{env:DEPLOY_ENV}})""#).unwrap();
+        let result = config
+            .for_channel("#general")
+            .rendered_username(&FixedHostname(Some("web01")), |_| None);
+        assert!(matches!(
+            result,
+            Err(SlackCliError::InvalidConfig(ref msg)) if msg.contains("DEPLOY_ENV is not set")
+        ));
+    }
+
+    #[test]
+    fn test_invalid_config_rejected() {
+        assert!(matches!(
+            CliConfig::parse("colour = \"red\""),
+            Err(SlackCliError::InvalidConfig(_))
+        ));
+        assert!(CliConfig::parse("color = ").is_err());
+    }
+
+    #[test]
+    fn test_top_level_defaults() {
+        let config = CliConfig::parse(
+            r##"
+channel = "#deploys"
+title = "Deploy"
+timeout = 10
+token_file = "~/.secrets/slack-token"
+color = "good"
+"##,
+        )
+        .unwrap();
+        assert_eq!(config.channel.as_deref(), Some("#deploys"));
+        assert_eq!(config.title.as_deref(), Some("Deploy"));
+        assert_eq!(config.timeout, Some(10));
+        assert_eq!(
+            config.token_file_in("/home/ci"),
+            Some(PathBuf::from("/home/ci/.secrets/slack-token"))
+        );
+        assert_eq!(
+            config.for_channel("#deploys").color.as_deref(),
+            Some("good")
+        );
+
+        let absolute = CliConfig::parse(r#"token_file = "/run/secrets/slack""#).unwrap();
+        assert_eq!(
+            absolute.token_file_in("/home/ci"),
+            Some(PathBuf::from("/run/secrets/slack"))
+        );
+        assert_eq!(CliConfig::default().token_file_in("/home/ci"), None);
+    }
+
+    #[test]
+    fn test_load_missing_explicit_file() {
+        let result = CliConfig::load(Path::new("/nonexistent/slack-cli.toml"));
+        assert!(
+            matches!(result, Err(SlackCliError::InvalidConfig(ref msg)) if msg.contains("/nonexistent"))
+        );
+    }
+}
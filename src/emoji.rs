@@ -0,0 +1,164 @@
+/// Shortcodes commonly used in alerts, mapped to their Unicode emoji.
+/// Unknown shortcodes are left untouched so Slack can still render custom ones.
+const SHORTCODES: &[(&str, &str)] = &[
+    ("+1", "👍"),
+    ("-1", "👎"),
+    ("bell", "🔔"),
+    ("boom", "💥"),
+    ("bug", "🐛"),
+    ("chart_with_downwards_trend", "📉"),
+    ("chart_with_upwards_trend", "📈"),
+    ("construction", "🚧"),
+    ("eyes", "👀"),
+    ("fire", "🔥"),
+    ("heart", "❤️"),
+    ("heavy_check_mark", "✔️"),
+    ("hourglass", "⌛"),
+    ("information_source", "ℹ️"),
+    ("large_green_circle", "🟢"),
+    ("large_yellow_circle", "🟡"),
+    ("no_entry", "⛔"),
+    ("red_circle", "🔴"),
+    ("rocket", "🚀"),
+    ("rotating_light", "🚨"),
+    ("skull", "💀"),
+    ("smile", "😄"),
+    ("sos", "🆘"),
+    ("stopwatch", "⏱️"),
+    ("tada", "🎉"),
+    ("thumbsdown", "👎"),
+    ("thumbsup", "👍"),
+    ("warning", "⚠️"),
+    ("white_check_mark", "✅"),
+    ("x", "❌"),
+    ("zap", "⚡"),
+];
+
+fn lookup(name: &str) -> Option<&'static str> {
+    SHORTCODES
+        .binary_search_by(|(code, _)| (*code).cmp(name))
+        .ok()
+        .map(|i| SHORTCODES[i].1)
+}
+
+fn is_shortcode_char(c: char) -> bool {
+    c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '+' | '-')
+}
+
+/// Replaces known `:shortcode:` sequences with their Unicode emoji.
+pub fn expand_shortcodes(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find(':') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let emoji = after.find(':').and_then(|end| {
+            let name = &after[..end];
+            let valid = !name.is_empty() && name.chars().all(is_shortcode_char);
+            valid.then(|| lookup(name)).flatten().map(|e| (e, end))
+        });
+
+        match emoji {
+            Some((emoji, end)) => {
+                out.push_str(emoji);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push(':');
+                rest = after;
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Where `:shortcode:` expansion applies. Slack renders shortcodes in mrkdwn
+/// sections itself, but not in the plain_text header, so the two are separate.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EmojiExpansion {
+    pub title: bool,
+    pub body: bool,
+}
+
+impl EmojiExpansion {
+    pub fn apply(&self, title: Option<String>, message: String) -> (Option<String>, String) {
+        let title = match title {
+            Some(t) if self.title => Some(expand_shortcodes(&t)),
+            other => other,
+        };
+        let message = if self.body {
+            expand_shortcodes(&message)
+        } else {
+            message
+        };
+        (title, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shortcode_table_is_sorted() {
+        assert!(SHORTCODES.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_expand_known_shortcodes() {
+        assert_eq!(
+            expand_shortcodes(":rotating_light: Disk full :fire:"),
+            "🚨 Disk full 🔥"
+        );
+        assert_eq!(expand_shortcodes("Deployed :+1:"), "Deployed 👍");
+    }
+
+    #[test]
+    fn test_unknown_and_partial_shortcodes_untouched() {
+        assert_eq!(
+            expand_shortcodes(":custom_party: at 10:30"),
+            ":custom_party: at 10:30"
+        );
+        assert_eq!(expand_shortcodes("a:b:tada:"), "a:b🎉");
+        assert_eq!(expand_shortcodes("::"), "::");
+    }
+
+    #[test]
+    fn test_title_only_expansion_keeps_body_shortcodes() {
+        let expansion = EmojiExpansion {
+            title: true,
+            body: false,
+        };
+        let (title, message) = expansion.apply(
+            Some(":warning: Degraded".to_string()),
+            "Replica lag :warning:".to_string(),
+        );
+        assert_eq!(title.as_deref(), Some("⚠️ Degraded"));
+        assert_eq!(message, "Replica lag :warning:");
+    }
+
+    #[test]
+    fn test_body_only_expansion_keeps_title_shortcodes() {
+        let expansion = EmojiExpansion {
+            title: false,
+            body: true,
+        };
+        let (title, message) = expansion.apply(
+            Some(":tada: Done".to_string()),
+            "All green :white_check_mark:".to_string(),
+        );
+        assert_eq!(title.as_deref(), Some(":tada: Done"));
+        assert_eq!(message, "All green ✅");
+    }
+
+    #[test]
+    fn test_default_expansion_is_noop() {
+        let (title, message) =
+            EmojiExpansion::default().apply(Some(":x:".to_string()), ":x:".to_string());
+        assert_eq!(title.as_deref(), Some(":x:"));
+        assert_eq!(message, ":x:");
+    }
+}
@@ -0,0 +1,287 @@
+use crate::slack::SlackClient;
+use crate::time::Clock;
+use crate::{send_message, SendConfig, SendResult, SlackCliError};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Default number of channels posted to at once
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// How one channel of a fan-out went, and how long its send took (retries included)
+pub struct ChannelOutcome {
+    pub channel: String,
+    pub result: Result<SendResult, SlackCliError>,
+    pub elapsed: Duration,
+}
+
+/// Sends each config from a pool of up to `concurrency` workers.
+/// Outcomes are returned in the same order as `configs`. With `fail_fast`, no
+/// new channel is started after a failure (sends already running still finish)
+/// and the rest are reported as `NotAttempted`.
+pub fn fan_out(
+    client: &(dyn SlackClient + Sync),
+    configs: &[SendConfig],
+    concurrency: usize,
+    fail_fast: bool,
+    clock: &(dyn Clock + Sync),
+) -> Vec<ChannelOutcome> {
+    let next = AtomicUsize::new(0);
+    let stopped = AtomicBool::new(false);
+    let slots: Mutex<Vec<Option<ChannelOutcome>>> =
+        Mutex::new(configs.iter().map(|_| None).collect());
+    let workers = concurrency.clamp(1, configs.len().max(1));
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(config) = configs.get(index) else {
+                    break;
+                };
+                if stopped.load(Ordering::Relaxed) {
+                    slots.lock().unwrap()[index] = Some(ChannelOutcome {
+                        channel: config.channel.clone(),
+                        result: Err(SlackCliError::NotAttempted),
+                        elapsed: Duration::ZERO,
+                    });
+                    continue;
+                }
+                let started = clock.now();
+                let result = send_message(client, config);
+                let elapsed = clock.now().duration_since(started).unwrap_or_default();
+                if fail_fast && result.is_err() {
+                    stopped.store(true, Ordering::Relaxed);
+                }
+                slots.lock().unwrap()[index] = Some(ChannelOutcome {
+                    channel: config.channel.clone(),
+                    result,
+                    elapsed,
+                });
+            });
+        }
+    });
+
+    slots
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|slot| slot.expect("every config is claimed by a worker"))
+        .collect()
+}
+
+/// One "Channel X: sent" line per outcome. With `timings`, each line carries
+/// its duration and the slowest channels come first.
+pub fn format_summary(outcomes: &[ChannelOutcome], timings: bool) -> String {
+    let mut order: Vec<&ChannelOutcome> = outcomes.iter().collect();
+    if timings {
+        order.sort_by_key(|o| std::cmp::Reverse(o.elapsed));
+    }
+
+    let mut out = String::new();
+    for outcome in order {
+        out.push_str(&format!("Channel {}: ", outcome.channel));
+        match outcome.result {
+            Ok(_) => out.push_str("sent"),
+            Err(SlackCliError::NotAttempted) => out.push_str("skipped"),
+            Err(ref e) => out.push_str(&format!("failed: {e}")),
+        }
+        if timings {
+            out.push_str(&format!(" ({}ms)", outcome.elapsed.as_millis()));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::slack::{ConversationsListResponse, SlackResponse};
+    use std::collections::HashMap;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// A clock that only moves when the mock client advances it
+    struct ManualClock(Mutex<SystemTime>);
+
+    impl Clock for ManualClock {
+        fn now(&self) -> SystemTime {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    /// Posting to a channel "takes" its configured latency on the shared clock
+    struct LatencyClient<'a> {
+        clock: &'a ManualClock,
+        latency_ms: HashMap<&'static str, u64>,
+        failing: Vec<&'static str>,
+        posted: Mutex<Vec<String>>,
+    }
+
+    impl SlackClient for LatencyClient<'_> {
+        fn post_message(
+            &self,
+            _token: &str,
+            payload: &[u8],
+        ) -> Result<SlackResponse, SlackCliError> {
+            let payload: serde_json::Value = serde_json::from_slice(payload).unwrap();
+            let channel = payload["channel"].as_str().unwrap().to_string();
+            let latency = self.latency_ms.get(channel.as_str()).copied().unwrap_or(0);
+            *self.clock.0.lock().unwrap() += Duration::from_millis(latency);
+            self.posted.lock().unwrap().push(channel.clone());
+
+            let failed = self.failing.contains(&channel.as_str());
+            Ok(SlackResponse {
+                ok: !failed,
+                error: failed.then(|| "channel_not_found".to_string()),
+                ..Default::default()
+            })
+        }
+
+        fn list_conversations(
+            &self,
+            _token: &str,
+            _types: &str,
+            _cursor: Option<&str>,
+        ) -> Result<ConversationsListResponse, SlackCliError> {
+            panic!("conversations.list not expected in this test")
+        }
+    }
+
+    fn configs(channels: &[&str]) -> Vec<SendConfig> {
+        channels
+            .iter()
+            .map(|channel| SendConfig {
+                channel: channel.to_string(),
+                message: "deploy finished".to_string(),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn client<'a>(clock: &'a ManualClock, latency_ms: &[(&'static str, u64)]) -> LatencyClient<'a> {
+        LatencyClient {
+            clock,
+            latency_ms: latency_ms.iter().copied().collect(),
+            failing: Vec::new(),
+            posted: Mutex::new(Vec::new()),
+        }
+    }
+
+    #[test]
+    fn test_fan_out_records_per_channel_durations() {
+        let clock = ManualClock(Mutex::new(UNIX_EPOCH));
+        let client = client(&clock, &[("#a", 120), ("#b", 1500), ("#c", 40)]);
+
+        // A single worker keeps the shared clock's advances attributable to one channel
+        let outcomes = fan_out(&client, &configs(&["#a", "#b", "#c"]), 1, false, &clock);
+
+        let timings: Vec<(&str, u128)> = outcomes
+            .iter()
+            .map(|o| (o.channel.as_str(), o.elapsed.as_millis()))
+            .collect();
+        assert_eq!(timings, vec![("#a", 120), ("#b", 1500), ("#c", 40)]);
+        assert!(outcomes.iter().all(|o| o.result.is_ok()));
+    }
+
+    #[test]
+    fn test_fan_out_parallel_sends_every_channel_once() {
+        let clock = ManualClock(Mutex::new(UNIX_EPOCH));
+        let client = client(&clock, &[]);
+        let channels = ["#a", "#b", "#c", "#d", "#e"];
+
+        let outcomes = fan_out(&client, &configs(&channels), 3, false, &clock);
+
+        let returned: Vec<&str> = outcomes.iter().map(|o| o.channel.as_str()).collect();
+        assert_eq!(returned, channels);
+        let mut posted = client.posted.into_inner().unwrap();
+        posted.sort();
+        assert_eq!(posted, channels);
+    }
+
+    #[test]
+    fn test_fan_out_failure_is_per_channel() {
+        let clock = ManualClock(Mutex::new(UNIX_EPOCH));
+        let mut client = client(&clock, &[]);
+        client.failing = vec!["#b"];
+
+        let outcomes = fan_out(&client, &configs(&["#a", "#b"]), 2, false, &clock);
+
+        assert!(outcomes[0].result.is_ok());
+        assert!(matches!(
+            outcomes[1].result,
+            Err(SlackCliError::SlackApiError(_))
+        ));
+    }
+
+    #[test]
+    fn test_format_summary_sorts_slowest_first_with_timings() {
+        let clock = ManualClock(Mutex::new(UNIX_EPOCH));
+        let mut client = client(&clock, &[("#a", 120), ("#b", 1500), ("#c", 40)]);
+        client.failing = vec!["#c"];
+        let outcomes = fan_out(&client, &configs(&["#a", "#b", "#c"]), 1, false, &clock);
+
+        assert_eq!(
+            format_summary(&outcomes, true),
+            "Channel #b: sent (1500ms)\n\
+             Channel #a: sent (120ms)\n\
+             Channel #c: failed: Slack API error: channel_not_found (40ms)\n"
+        );
+        assert_eq!(
+            format_summary(&outcomes, false),
+            "Channel #a: sent\n\
+             Channel #b: sent\n\
+             Channel #c: failed: Slack API error: channel_not_found\n"
+        );
+    }
+
+    #[test]
+    fn test_fan_out_continues_after_failure_by_default() {
+        let clock = ManualClock(Mutex::new(UNIX_EPOCH));
+        let mut client = client(&clock, &[]);
+        client.failing = vec!["#a"];
+
+        let outcomes = fan_out(&client, &configs(&["#a", "#b", "#c"]), 1, false, &clock);
+
+        assert!(outcomes[0].result.is_err());
+        assert!(outcomes[1].result.is_ok());
+        assert!(outcomes[2].result.is_ok());
+    }
+
+    #[test]
+    fn test_fan_out_fail_fast_skips_remaining_channels() {
+        let clock = ManualClock(Mutex::new(UNIX_EPOCH));
+        let mut client = client(&clock, &[]);
+        client.failing = vec!["#b"];
+
+        let outcomes = fan_out(
+            &client,
+            &configs(&["#a", "#b", "#c", "#d"]),
+            1,
+            true,
+            &clock,
+        );
+
+        assert_eq!(*client.posted.lock().unwrap(), vec!["#a", "#b"]);
+        assert!(outcomes[0].result.is_ok());
+        assert!(matches!(
+            outcomes[1].result,
+            Err(SlackCliError::SlackApiError(_))
+        ));
+        assert!(matches!(
+            outcomes[2].result,
+            Err(SlackCliError::NotAttempted)
+        ));
+        assert!(matches!(
+            outcomes[3].result,
+            Err(SlackCliError::NotAttempted)
+        ));
+        assert_eq!(
+            format_summary(&outcomes, false),
+            "Channel #a: sent\n\
+             Channel #b: failed: Slack API error: channel_not_found\n\
+             Channel #c: skipped\n\
+             Channel #d: skipped\n"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use std::env;
+use std::fs;
+use std::process::Command;
+
+pub const UNKNOWN_HOSTNAME: &str = "unknown";
+
+pub trait HostnameSource {
+    fn hostname(&self) -> Option<String>;
+}
+
+/// A hostname source for tests that always gives the same answer
+#[cfg(test)]
+pub(crate) struct FixedHostname(pub(crate) Option<&'static str>);
+
+#[cfg(test)]
+impl HostnameSource for FixedHostname {
+    fn hostname(&self) -> Option<String> {
+        self.0.map(str::to_string)
+    }
+}
+
+/// Looks up the hostname from `$HOSTNAME`, the kernel, `/etc/hostname`, then the `hostname` command
+pub struct SystemHostname;
+
+impl HostnameSource for SystemHostname {
+    fn hostname(&self) -> Option<String> {
+        let candidates = [
+            || env::var("HOSTNAME").ok(),
+            || fs::read_to_string("/proc/sys/kernel/hostname").ok(),
+            || fs::read_to_string("/etc/hostname").ok(),
+            || {
+                Command::new("hostname")
+                    .output()
+                    .ok()
+                    .filter(|o| o.status.success())
+                    .and_then(|o| String::from_utf8(o.stdout).ok())
+            },
+        ];
+
+        candidates
+            .iter()
+            .filter_map(|lookup| lookup())
+            .map(|name| name.trim().to_string())
+            .find(|name| !name.is_empty())
+    }
+}
+
+/// Returns the hostname, or "unknown" when it cannot be determined
+pub fn resolve_hostname(source: &dyn HostnameSource) -> String {
+    source
+        .hostname()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| UNKNOWN_HOSTNAME.to_string())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HostnamePlacement {
+    /// Prepend `[host] ` to the message text
+    Prefix,
+    /// Add a `host: <name>` line to the footer context block
+    Footer,
+}
+
+/// Tags a message with the originating hostname, returning the (possibly prefixed) message
+pub fn tag_hostname(
+    message: String,
+    context: &mut Vec<String>,
+    hostname: &str,
+    placement: HostnamePlacement,
+) -> String {
+    match placement {
+        HostnamePlacement::Prefix => format!("[{hostname}] {message}"),
+        HostnamePlacement::Footer => {
+            context.push(format!("host: {hostname}"));
+            message
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_hostname_trims() {
+        assert_eq!(resolve_hostname(&FixedHostname(Some("web01\n"))), "web01");
+    }
+
+    #[test]
+    fn test_resolve_hostname_falls_back_to_unknown() {
+        assert_eq!(resolve_hostname(&FixedHostname(None)), UNKNOWN_HOSTNAME);
+        assert_eq!(
+            resolve_hostname(&FixedHostname(Some("  "))),
+            UNKNOWN_HOSTNAME
+        );
+    }
+
+    #[test]
+    fn test_tag_hostname_prefix() {
+        let mut context = Vec::new();
+        let hostname = resolve_hostname(&FixedHostname(Some("web01")));
+        let message = tag_hostname(
+            "Deploy complete".to_string(),
+            &mut context,
+            &hostname,
+            HostnamePlacement::Prefix,
+        );
+        assert_eq!(message, "[web01] Deploy complete");
+        assert!(context.is_empty());
+    }
+
+    #[test]
+    fn test_tag_hostname_footer() {
+        let mut context = vec!["(updated 5m ago)".to_string()];
+        let message = tag_hostname(
+            "Deploy complete".to_string(),
+            &mut context,
+            "web01",
+            HostnamePlacement::Footer,
+        );
+        assert_eq!(message, "Deploy complete");
+        assert_eq!(context, vec!["(updated 5m ago)", "host: web01"]);
+    }
+}
@@ -0,0 +1,75 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Severity of a log event. Without the `tracing` feature, `-v` shows every level
+/// up to `DEBUG` and `-vv` adds `TRACE`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Level(u8);
+
+impl Level {
+    pub const WARN: Level = Level(1);
+    pub const INFO: Level = Level(1);
+    pub const DEBUG: Level = Level(1);
+    pub const TRACE: Level = Level(2);
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+/// Sets how many `-v` flags were given; 0 (the default) logs nothing
+pub fn set_verbosity(verbosity: u8) {
+    VERBOSITY.store(verbosity, Ordering::Relaxed);
+}
+
+/// Whether events at `level` are printed by the built-in logger
+pub fn enabled(level: Level) -> bool {
+    level.0 <= VERBOSITY.load(Ordering::Relaxed)
+}
+
+/// Logs "message key=value ..." as a `tracing` event with the `tracing` feature,
+/// otherwise to stderr through the built-in logger. Values take `%` (Display) or
+/// `?` (Debug), like `tracing` fields.
+macro_rules! log_event {
+    ($level:ident, $msg:expr $(, $key:ident = $sigil:tt $value:expr)* $(,)?) => {{
+        #[cfg(feature = "tracing")]
+        tracing::event!(tracing::Level::$level, $($key = $sigil $value,)* "{}", $msg);
+        #[cfg(not(feature = "tracing"))]
+        if $crate::log::enabled($crate::log::Level::$level) {
+            let mut line = format!("[{}] {}", stringify!($level), $msg);
+            $(line.push_str(&format!(
+                concat!(" ", stringify!($key), "={}"),
+                $crate::log::log_value!($sigil $value)
+            ));)*
+            eprintln!("{line}");
+        }
+    }};
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! log_value {
+    (% $value:expr) => {
+        format!("{}", $value)
+    };
+    (? $value:expr) => {
+        format!("{:?}", $value)
+    };
+}
+
+pub(crate) use log_event;
+#[cfg(not(feature = "tracing"))]
+pub(crate) use log_value;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbosity_enables_levels() {
+        set_verbosity(0);
+        assert!(!enabled(Level::DEBUG));
+        set_verbosity(1);
+        assert!(enabled(Level::WARN) && enabled(Level::DEBUG));
+        assert!(!enabled(Level::TRACE));
+        set_verbosity(2);
+        assert!(enabled(Level::TRACE));
+        set_verbosity(0);
+    }
+}
@@ -1,10 +1,35 @@
 use clap::Parser;
 use serde_json::Value;
-use slack_cli::slack::HttpSlackClient;
-use slack_cli::token::resolve_token;
-use slack_cli::{send_message, SendConfig, SlackCliError};
+use slack_cli::ansi::strip_ansi;
+use slack_cli::channel::{
+    decorate_channel, format_conversations, list_all_conversations, open_dm, ChannelResolver,
+    DEFAULT_CONVERSATION_TYPES,
+};
+use slack_cli::charset::{decode_input, OnInvalid};
+use slack_cli::color::{color_for_source, format_color_list, use_swatches};
+use slack_cli::command::{run_command, CommandReport};
+use slack_cli::config::{ChannelDefaults, CliConfig, Defaults};
+use slack_cli::emoji::EmojiExpansion;
+use slack_cli::fanout::{fan_out, format_summary, DEFAULT_CONCURRENCY};
+use slack_cli::host::{resolve_hostname, tag_hostname, HostnamePlacement, SystemHostname};
+use slack_cli::retry::{RetryPolicy, RetryProfile};
+use slack_cli::slack::{
+    curl_command, AttachmentField, HttpClientConfig, HttpSlackClient, SectionField,
+    WebhookSlackClient,
+};
+use slack_cli::state::{content_hash, read_state, should_send, write_state, Outcome, SendState};
+use slack_cli::time::{since_footer, Clock, SystemClock};
+use slack_cli::token::{resolve_token_with_warning, TokenConfig};
+use slack_cli::{
+    collapse_repeats, delete_message, parse_attachment_ts, plan_send, send_message, send_mirrored,
+    send_summarized, validate_schedule_time, validate_ts, AttachmentOverflow, OutputFormat,
+    PayloadShape, SendConfig, SendReport, SendResult, SlackCliError, SplitStrategy,
+    DEFAULT_MAX_ATTACHMENTS,
+};
 use std::io::{self, IsTerminal, Read};
+use std::path::PathBuf;
 use std::process;
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(
@@ -14,32 +39,602 @@ use std::process;
     before_help = concat!("slack-cli v", env!("CARGO_PKG_VERSION")),
 )]
 struct Args {
-    /// Channel name or ID (e.g. "#general" or "C01234567")
-    #[arg(short, long)]
-    channel: String,
+    /// Channel name or ID (e.g. "#general" or "C01234567"); repeat or comma-separate to post to several
+    #[arg(short, long, value_delimiter = ',')]
+    channel: Vec<String>,
+
+    /// With several channels, stop starting new sends after the first failure
+    #[arg(long)]
+    fail_fast: bool,
+
+    /// Message these user IDs directly instead of a channel; several (comma-separated) get a group DM
+    #[arg(
+        long,
+        value_name = "USERS",
+        value_delimiter = ',',
+        conflicts_with_all = [
+            "channel", "also_channels", "channel_prefix", "channel_suffix", "channel_suffix_env",
+            "webhook_url", "list_conversations", "print_channel_id", "resolve_channel",
+            "delete_ts",
+        ]
+    )]
+    dm: Vec<String>,
+
+    /// Prepended to channel names (not IDs or @users), e.g. "payments-"
+    #[arg(long, value_name = "STR")]
+    channel_prefix: Option<String>,
+
+    /// Appended to channel names (not IDs or @users), e.g. "-prod"
+    #[arg(long, value_name = "STR", allow_hyphen_values = true)]
+    channel_suffix: Option<String>,
+
+    /// Read the channel suffix from this environment variable
+    #[arg(long, value_name = "VAR", conflicts_with = "channel_suffix")]
+    channel_suffix_env: Option<String>,
+
+    /// Also post to this channel (repeatable); all channels are sent in parallel
+    #[arg(
+        long = "also-channel",
+        value_name = "CHANNEL",
+        conflicts_with_all = ["correlation_key", "thread_ts", "mirror_webhook", "print_channel_id"]
+    )]
+    also_channels: Vec<String>,
+
+    /// Maximum number of channels posted to at once
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_CONCURRENCY,
+          value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    concurrency: usize,
+
+    /// Print how long each channel took, slowest first
+    #[arg(long)]
+    stats: bool,
+
+    /// Charset of the message read from stdin, e.g. windows-1252 (default: UTF-8)
+    #[arg(long, value_name = "CHARSET")]
+    input_encoding: Option<String>,
+
+    /// What to do with bytes that aren't valid in the input charset: replace or error
+    #[arg(long, value_name = "MODE", default_value = "error")]
+    on_invalid: OnInvalid,
+
+    /// Workspace profile: read SLACK_API_KEY_<NAME> and ~/.slack/<name>/api-token instead
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Slack token to use instead of every other source (visible in process listings)
+    #[arg(long, value_name = "TOKEN", conflicts_with_all = ["token_command", "token_from_keyring"])]
+    token: Option<String>,
+
+    /// Run this shell command and use its output as the token (also SLACK_TOKEN_COMMAND)
+    #[arg(long, value_name = "CMD")]
+    token_command: Option<String>,
+
+    /// Read the token only from the OS keychain (service "slack-cli", account "api-token")
+    #[arg(long, conflicts_with = "token_command")]
+    token_from_keyring: bool,
 
     /// Message text (reads from stdin if omitted)
     #[arg(short, long)]
     message: Option<String>,
 
-    /// Hex color for attachment sidebar (e.g. "#FF0000")
+    /// Pick the color from a fixed palette by hashing this string (e.g. a service name)
+    #[arg(long, value_name = "STR", conflicts_with = "color")]
+    color_by: Option<String>,
+
+    /// Run this shell command and send its output, titled with the command and colored by its exit status
+    #[arg(long, value_name = "CMD", conflicts_with_all = ["message", "blocks", "blocks_from_url"])]
+    run: Option<String>,
+
+    /// Wrap the --run output in a code block
+    #[arg(long, requires = "run")]
+    run_code_block: bool,
+
+    /// Hex color or keyword for the attachment sidebar (e.g. "#FF0000", "danger"), or "auto" to follow the log level
     #[arg(long)]
     color: Option<String>,
 
+    /// Display name to post as (needs the chat:write.customize scope)
+    #[arg(long)]
+    username: Option<String>,
+
+    /// Emoji to post with instead of the app's icon, e.g. :robot_face: (needs the chat:write.customize scope)
+    #[arg(long, value_name = "EMOJI")]
+    icon_emoji: Option<String>,
+
+    /// http(s) image to post with instead of the app's icon (needs the chat:write.customize scope)
+    #[arg(long, value_name = "URL", conflicts_with = "icon_emoji")]
+    icon_url: Option<String>,
+
     /// Title displayed as a header above the message
     #[arg(short, long)]
     title: Option<String>,
 
     /// JSON blocks file (reads from stdin if omitted)
-    #[arg(long, num_args = 0..=1, default_missing_value = "-", conflicts_with = "title")]
+    #[arg(long, num_args = 0..=1, default_missing_value = "-")]
     blocks: Option<String>,
+
+    /// Fetch the JSON blocks array from a URL
+    #[arg(long, value_name = "URL", conflicts_with = "blocks")]
+    blocks_from_url: Option<String>,
+
+    /// JSON file with an array of attachment objects to send verbatim (reads from stdin if omitted)
+    #[arg(long, value_name = "PATH", num_args = 0..=1, default_missing_value = "-")]
+    attachments: Option<String>,
+
+    /// Maximum number of blocks accepted from --blocks/--blocks-from-url
+    #[arg(long, alias = "block-limit", value_name = "N", default_value_t = DEFAULT_MAX_BLOCKS)]
+    max_blocks: usize,
+
+    /// Most attachments a message may carry before --on-too-many-attachments applies
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_MAX_ATTACHMENTS)]
+    max_attachments: usize,
+
+    /// Over --max-attachments: error (default) or warn and send anyway
+    #[arg(long, value_name = "MODE", default_value = "error")]
+    on_too_many_attachments: AttachmentOverflow,
+
+    /// Notification text for colored attachments (defaults to the message's first line)
+    #[arg(long, value_name = "STR", requires = "color")]
+    attachment_fallback: Option<String>,
+
+    /// Leave the top-level text of colored messages blank; plain messages keep theirs
+    #[arg(long, conflicts_with = "fallback_text")]
+    no_fallback_text: bool,
+
+    /// Put a divider line between the title and the message
+    #[arg(long)]
+    divider: bool,
+
+    /// Strip mrkdwn markers (*, _, ~, `) from the title, which headers show literally
+    #[arg(long)]
+    clean_title: bool,
+
+    /// Shorten a title over Slack's 150-character header limit with "…" instead of failing
+    #[arg(long)]
+    truncate_title: bool,
+
+    /// Top-level message text, shown in push notifications (defaults to the message, or the title)
+    #[arg(long, value_name = "STR")]
+    fallback_text: Option<String>,
+
+    /// Classic attachment field as "Title=Value" (repeatable, only rendered with --color)
+    #[arg(long = "attach-field", value_name = "TITLE=VALUE")]
+    attach_fields: Vec<String>,
+
+    /// Render attachment fields side by side (short: true)
+    #[arg(long, requires = "attach_fields")]
+    attach_field_short: bool,
+
+    /// Muted attribution line under a colored attachment (ignored with a warning without --color)
+    #[arg(long, value_name = "TEXT")]
+    footer: Option<String>,
+
+    /// Icon URL shown before --footer
+    #[arg(long, value_name = "URL", requires = "footer")]
+    footer_icon: Option<String>,
+
+    /// mrkdwn line shown above a colored attachment's bar (ignored without --color)
+    #[arg(long, value_name = "TEXT")]
+    pretext: Option<String>,
+
+    /// Timestamp shown next to a colored attachment's footer: Unix epoch seconds or "now" (ignored without --color)
+    #[arg(long, value_name = "EPOCH", value_parser = parse_attachment_ts_arg)]
+    attachment_ts: Option<i64>,
+
+    /// Author line above a colored attachment, e.g. the alerting system (ignored without --color)
+    #[arg(long, value_name = "TEXT")]
+    author_name: Option<String>,
+
+    /// http(s) link for --author-name
+    #[arg(long, value_name = "URL", requires = "author_name")]
+    author_link: Option<String>,
+
+    /// http(s) icon URL shown before --author-name
+    #[arg(long, value_name = "URL", requires = "author_name")]
+    author_icon: Option<String>,
+
+    /// Key/value pair shown in two columns after the message, as "Key:Value" (repeatable, up to 10)
+    #[arg(long = "field", value_name = "KEY:VALUE")]
+    section_fields: Vec<String>,
+
+    /// Image to show after the message, as an http(s) URL
+    #[arg(long = "image", value_name = "URL", requires = "image_alt")]
+    image_url: Option<String>,
+
+    /// Alt text for --image, read by screen readers and shown when the image can't load
+    #[arg(long, value_name = "TEXT", requires = "image_url")]
+    image_alt: Option<String>,
+
+    /// Muted footer line shown in a context block below the message (repeatable, up to 10)
+    #[arg(long = "context", value_name = "TEXT")]
+    context_lines: Vec<String>,
+
+    /// Append "(updated Nm ago)" to the footer, measured from an RFC 3339 or epoch timestamp
+    #[arg(long, value_name = "TIMESTAMP")]
+    since: Option<String>,
+
+    /// Remove ANSI color/cursor escape sequences from the message (for piped CI logs)
+    #[arg(long)]
+    strip_ansi: bool,
+
+    /// Fold runs of identical consecutive lines into "line (xN)"
+    #[arg(long)]
+    collapse_repeats: bool,
+
+    /// Minimum run length folded by --collapse-repeats
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 2,
+        requires = "collapse_repeats"
+    )]
+    collapse_min: usize,
+
+    /// Where long messages are split into sections: char, line or paragraph
+    #[arg(long, value_name = "STRATEGY", default_value = "char")]
+    split_strategy: SplitStrategy,
+
+    /// Resolve --channel "#name" to its ID, print it, and exit without sending
+    #[arg(long)]
+    print_channel_id: bool,
+
+    /// Send input blocks and input-only elements, which Slack only renders in modals and App Home
+    #[cfg(feature = "block-validation")]
+    #[arg(long)]
+    allow_input_blocks: bool,
+
+    /// Check --blocks against the Block Kit layout locally, report every error, and exit without sending
+    #[cfg(feature = "block-validation")]
+    #[arg(long, conflicts_with = "dry_run")]
+    schema_validate: bool,
+
+    /// List the conversations visible to the token and exit without sending
+    #[arg(long, conflicts_with = "print_channel_id")]
+    list_conversations: bool,
+
+    /// Print the --color keywords with their hex values and exit
+    #[arg(long)]
+    list_colors: bool,
+
+    /// Never draw ANSI color swatches (also disabled by NO_COLOR)
+    #[arg(long)]
+    no_color_output: bool,
+
+    /// Conversation types to list or resolve names against (comma-separated)
+    #[arg(long, value_name = "TYPES", default_value = DEFAULT_CONVERSATION_TYPES)]
+    resolve_types: String,
+
+    /// Look up the ID of each #name channel before posting (needs channels:read)
+    #[arg(long)]
+    resolve_channel: bool,
+
+    /// Output format for sends, --list-conversations and --dry-run: text or json
+    #[arg(long, value_name = "FORMAT", default_value = "text")]
+    output: OutputFormat,
+
+    /// Retry preset: none, conservative or aggressive; the other retry flags override it
+    #[arg(long, value_name = "PROFILE")]
+    retry_profile: Option<RetryProfile>,
+
+    /// Retries after a failed attempt (default 3, or the --retry-profile's)
+    #[arg(long, value_name = "N")]
+    max_retries: Option<u32>,
+
+    /// Wait out rate limits indefinitely instead of giving up after --max-retries
+    #[arg(long)]
+    wait_for_rate_limit: bool,
+
+    /// Stop retrying once the next wait would exceed this many seconds in total
+    #[arg(long, value_name = "SECS")]
+    deadline_secs: Option<u64>,
+
+    /// Cap any single Retry-After wait at this many seconds
+    #[arg(long, value_name = "SECS")]
+    max_retry_delay: Option<u64>,
+
+    /// Total time allowed for each HTTP request, in seconds; 0 for none (default 30, also SLACK_CLI_TIMEOUT)
+    #[arg(long, alias = "timeout", value_name = "SECS")]
+    timeout_secs: Option<u64>,
+
+    /// Time allowed to resolve and connect to the server, in seconds
+    #[arg(long, value_name = "SECS")]
+    connect_timeout_secs: Option<u64>,
+
+    /// Send every request through this proxy instead of HTTPS_PROXY/HTTP_PROXY (NO_PROXY still applies)
+    #[arg(long, value_name = "URL")]
+    proxy: Option<String>,
+
+    /// Add a random delay of up to this many milliseconds to each rate-limit wait
+    #[arg(long, value_name = "MS")]
+    retry_jitter_ms: Option<u64>,
+
+    /// Seed the retry jitter for reproducible delays (testing; also SLACK_CLI_JITTER_SEED)
+    #[arg(long, value_name = "N", hide = true)]
+    retry_jitter_seed: Option<u64>,
+
+    /// Always send Block Kit blocks, even with --color (the color is dropped)
+    #[arg(long, conflicts_with = "force_attachment")]
+    force_blocks: bool,
+
+    /// Always send a colored attachment, even past the 4000-char limit (Slack may truncate)
+    #[arg(long, requires = "color")]
+    force_attachment: bool,
+
+    /// Unfurl links and media for this message even if disabled by default
+    #[arg(long)]
+    unfurl: bool,
+
+    /// Don't unfurl links or media into previews, e.g. for log dumps full of URLs
+    #[arg(long, conflicts_with_all = ["unfurl", "unfurl_links", "unfurl_media"])]
+    no_unfurl: bool,
+
+    /// Unfurl links for this message even if disabled by SLACK_CLI_UNFURL_LINKS
+    #[arg(long)]
+    unfurl_links: bool,
+
+    /// Unfurl media for this message even if disabled by SLACK_CLI_UNFURL_MEDIA
+    #[arg(long)]
+    unfurl_media: bool,
+
+    /// Workspace (team) ID to post as, for Slack Connect channels shared by several teams
+    #[arg(long, value_name = "TEAM_ID")]
+    team_id: Option<String>,
+
+    /// Prefix the message with "[<hostname>] "
+    #[arg(long)]
+    tag_hostname: bool,
+
+    /// Put the hostname in the footer instead of prefixing the message
+    #[arg(long, requires = "tag_hostname")]
+    hostname_in_footer: bool,
+
+    /// Replace known :shortcodes: in the title with Unicode emoji (plain_text headers don't render them)
+    #[arg(long)]
+    expand_emoji_in_title: bool,
+
+    /// Replace known :shortcodes: in the message body with Unicode emoji
+    #[arg(long)]
+    expand_emoji_in_body: bool,
+
+    /// Log sends, retries and API responses to stderr; repeat (-vv) to include
+    /// request and response bodies
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Post only the first N lines, with the full message as replies in its thread
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+        conflicts_with_all = ["blocks", "blocks_from_url", "also_channels", "mirror_webhook"]
+    )]
+    summary_lines: Option<usize>,
+
+    /// Queue the message for this Unix time (chat.scheduleMessage) and print its scheduled message ID
+    #[arg(
+        long,
+        value_name = "UNIX_TS",
+        conflicts_with_all = [
+            "update_ts", "delete_ts", "ephemeral_user", "correlation_key", "summary_lines",
+            "also_channels",
+        ]
+    )]
+    schedule_at: Option<u64>,
+
+    /// Post an ephemeral message (chat.postEphemeral) that only this user ID sees
+    #[arg(
+        long,
+        value_name = "USER_ID",
+        conflicts_with_all = ["update_ts", "delete_ts", "correlation_key", "summary_lines"]
+    )]
+    ephemeral_user: Option<String>,
+
+    /// Delete the message at this ts (chat.delete) and exit; --channel must be an ID
+    #[arg(
+        long,
+        value_name = "TS",
+        value_parser = parse_ts("--delete-ts"),
+        conflicts_with_all = [
+            "message", "blocks", "blocks_from_url", "run", "update_ts", "also_channels",
+            "print_channel_id", "dry_run",
+        ]
+    )]
+    delete_ts: Option<String>,
+
+    /// Edit the message at this ts (chat.update) instead of posting; --channel must be an ID
+    #[arg(
+        long,
+        value_name = "TS",
+        value_parser = parse_ts("--update-ts"),
+        conflicts_with_all = [
+            "color", "color_by", "thread_ts", "correlation_key", "summary_lines",
+            "also_channels", "mirror_webhook",
+        ]
+    )]
+    update_ts: Option<String>,
+
+    /// Reply in the thread of this message, e.g. 1699999999.000100
+    #[arg(long, value_name = "TS", value_parser = parse_ts("--thread-ts"), conflicts_with = "correlation_key")]
+    thread_ts: Option<String>,
+
+    /// Also show the --thread-ts reply in the channel
+    #[arg(long, requires = "thread_ts")]
+    reply_broadcast: bool,
+
+    /// React to the sent message with this emoji, e.g. :white_check_mark: (needs reactions:write)
+    #[arg(
+        long,
+        value_name = "EMOJI",
+        conflicts_with_all = ["webhook_url", "schedule_at", "ephemeral_user", "delete_ts"]
+    )]
+    react: Option<String>,
+
+    /// Pin the sent message to its channel, e.g. the current on-call roster (needs pins:write)
+    #[arg(
+        long,
+        conflicts_with_all = ["webhook_url", "schedule_at", "ephemeral_user", "delete_ts"]
+    )]
+    pin: bool,
+
+    /// Thread under the latest message tagged with this key (found via channel history), or tag this message as the parent
+    #[arg(long, value_name = "KEY")]
+    correlation_key: Option<String>,
+
+    /// Also post the same payload to this webhook URL; the exit code follows the channel send
+    #[arg(long, value_name = "URL")]
+    mirror_webhook: Option<String>,
+
+    /// Post through this incoming webhook instead of the Web API; needs no token, and --channel
+    /// becomes optional. SLACK_WEBHOOK_URL stands in when no token is configured.
+    #[arg(
+        long,
+        value_name = "URL",
+        conflicts_with_all = [
+            "list_conversations", "print_channel_id", "delete_ts", "update_ts", "schedule_at",
+            "ephemeral_user", "correlation_key", "summary_lines", "also_channels",
+            "mirror_webhook", "print_curl", "resolve_channel",
+        ]
+    )]
+    webhook_url: Option<String>,
+
+    /// Config file with global and per-channel defaults (default: ~/.config/slack-cli/config.toml)
+    #[arg(long, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Print the payload that would be posted instead of sending it (no token needed)
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Print an equivalent curl command to stderr before sending (token shown as $SLACK_TOKEN)
+    #[arg(long, alias = "print-request-curl")]
+    print_curl: bool,
+
+    /// Record each run's outcome and content hash in this JSON file
+    #[arg(long, value_name = "PATH")]
+    state_file: Option<PathBuf>,
+
+    /// Skip sending when the last run succeeded with the same content
+    #[arg(long, requires = "state_file")]
+    only_on_change: bool,
+}
+
+/// A value parser for a message-timestamp flag, naming `flag` when a value is malformed
+fn parse_ts(flag: &'static str) -> impl Fn(&str) -> Result<String, SlackCliError> + Clone {
+    move |ts| {
+        validate_ts(flag, ts)?;
+        Ok(ts.to_string())
+    }
 }
 
-fn read_stdin() -> Result<String, SlackCliError> {
-    let mut buffer = String::new();
+fn inline_token(token: &str) -> Result<String, SlackCliError> {
+    match token.trim() {
+        "" => Err(SlackCliError::InvalidInput("--token is empty".to_string())),
+        token => Ok(token.to_string()),
+    }
+}
+
+fn parse_attachment_ts_arg(input: &str) -> Result<i64, SlackCliError> {
+    parse_attachment_ts(input, SystemClock.now())
+}
+
+/// Used in place of --webhook-url when nothing needs the Web API
+const WEBHOOK_URL_ENV: &str = "SLACK_WEBHOOK_URL";
+
+/// The webhook `env_url` (from SLACK_WEBHOOK_URL) posts through when --webhook-url wasn't
+/// given. A set variable mustn't take over token-based use, so it only applies when no
+/// Web API-only flag is set and `has_token` finds no token.
+fn webhook_from_env(
+    args: &Args,
+    env_url: Option<String>,
+    has_token: impl FnOnce() -> bool,
+) -> Option<String> {
+    let env_url = env_url.filter(|url| !url.trim().is_empty())?;
+    let needs_web_api = args.list_conversations
+        || args.print_channel_id
+        || args.resolve_channel
+        || args.delete_ts.is_some()
+        || args.update_ts.is_some()
+        || args.schedule_at.is_some()
+        || args.ephemeral_user.is_some()
+        || args.correlation_key.is_some()
+        || args.summary_lines.is_some()
+        || args.mirror_webhook.is_some()
+        || args.print_curl
+        || args.react.is_some()
+        || args.pin
+        || !args.dm.is_empty()
+        || !args.also_channels.is_empty()
+        || args.channel.len() > 1;
+    if args.webhook_url.is_some() || needs_web_api || args.token.is_some() || has_token() {
+        return None;
+    }
+    Some(env_url)
+}
+
+/// Several --channel values fan out like --also-channel, so the flags clap keeps
+/// away from --also-channel are rejected here too
+fn check_single_channel_flags(args: &Args) -> Result<(), SlackCliError> {
+    if args.channel.len() < 2 {
+        return Ok(());
+    }
+    let single_only = [
+        (args.correlation_key.is_some(), "--correlation-key"),
+        (args.thread_ts.is_some(), "--thread-ts"),
+        (args.mirror_webhook.is_some(), "--mirror-webhook"),
+        (args.webhook_url.is_some(), "--webhook-url"),
+        (args.print_channel_id, "--print-channel-id"),
+        (args.summary_lines.is_some(), "--summary-lines"),
+        (args.update_ts.is_some(), "--update-ts"),
+        (args.delete_ts.is_some(), "--delete-ts"),
+        (args.schedule_at.is_some(), "--schedule-at"),
+    ];
+    match single_only.iter().find(|(set, _)| *set) {
+        Some((_, flag)) => Err(SlackCliError::InvalidConfig(format!(
+            "{flag} can't be used with several channels"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// `unfurl_links` / `unfurl_media` for the payload; `None` leaves Slack's default
+fn unfurl_settings(args: &Args, defaults: &Defaults) -> (Option<bool>, Option<bool>) {
+    if args.no_unfurl {
+        return (Some(false), Some(false));
+    }
+    defaults.unfurl(
+        args.unfurl || args.unfurl_links,
+        args.unfurl || args.unfurl_media,
+    )
+}
+
+/// The --retry-profile preset (or the plain defaults) with individual flags applied on top
+fn retry_policy(args: &Args, defaults: &Defaults) -> RetryPolicy {
+    let mut policy = args
+        .retry_profile
+        .map_or_else(RetryPolicy::default, RetryProfile::policy);
+    if let Some(max_retries) = args.max_retries {
+        policy.max_retries = max_retries;
+    }
+    policy.wait_for_rate_limit |= args.wait_for_rate_limit;
+    if let Some(secs) = args.deadline_secs {
+        policy.deadline = Some(Duration::from_secs(secs));
+    }
+    if let Some(secs) = args.max_retry_delay {
+        policy.max_delay = Some(Duration::from_secs(secs));
+    }
+    if let Some(ms) = args.retry_jitter_ms {
+        policy.jitter = Some(Duration::from_millis(ms));
+    }
+    policy.jitter_seed = args.retry_jitter_seed.or(defaults.jitter_seed);
+    policy
+}
+
+fn read_stdin(charset: Option<&str>, on_invalid: OnInvalid) -> Result<String, SlackCliError> {
+    let mut bytes = Vec::new();
     io::stdin()
-        .read_to_string(&mut buffer)
+        .read_to_end(&mut bytes)
         .map_err(SlackCliError::StdinError)?;
+    let buffer = decode_input(&bytes, charset, on_invalid)?;
     let trimmed = buffer.trim().to_string();
     if trimmed.is_empty() {
         return Err(SlackCliError::NoMessage);
@@ -47,10 +642,44 @@ fn read_stdin() -> Result<String, SlackCliError> {
     Ok(trimmed)
 }
 
-fn parse_blocks_json(json_str: &str) -> Result<Vec<Value>, SlackCliError> {
+const DEFAULT_MAX_BLOCKS: usize = slack_cli::MESSAGE_BLOCKS_MAX;
+
+/// Block schema versions accepted in the `{"version": ..., "blocks": [...]}` wrapper
+const SUPPORTED_BLOCKS_VERSIONS: &[&str] = &["1"];
+
+/// Accepts a plain blocks array or a `{"version": "1", "blocks": [...]}` wrapper.
+/// An unsupported wrapper version is returned as a warning; the blocks are still used.
+fn parse_blocks_json(
+    json_str: &str,
+    max_blocks: usize,
+) -> Result<(Vec<Value>, Option<String>), SlackCliError> {
     let value: Value = serde_json::from_str(json_str)
         .map_err(|e| SlackCliError::InvalidBlocksJson(e.to_string()))?;
 
+    let mut warning = None;
+    let value = match value {
+        Value::Object(mut wrapper) if wrapper.contains_key("blocks") => {
+            let version = match wrapper.get("version") {
+                Some(Value::String(v)) => Some(v.clone()),
+                Some(Value::Number(n)) => Some(n.to_string()),
+                Some(_) => {
+                    return Err(SlackCliError::InvalidBlocksJson(
+                        "\"version\" must be a string".to_string(),
+                    ))
+                }
+                None => None,
+            };
+            if let Some(v) = version.filter(|v| !SUPPORTED_BLOCKS_VERSIONS.contains(&v.as_str())) {
+                warning = Some(format!(
+                    "blocks schema version '{v}' is not supported (expected {}); sending anyway",
+                    SUPPORTED_BLOCKS_VERSIONS.join(", ")
+                ));
+            }
+            wrapper.remove("blocks").unwrap_or_default()
+        }
+        other => other,
+    };
+
     let arr = value
         .as_array()
         .ok_or_else(|| SlackCliError::InvalidBlocksJson("expected a JSON array".to_string()))?;
@@ -61,10 +690,10 @@ fn parse_blocks_json(json_str: &str) -> Result<Vec<Value>, SlackCliError> {
         ));
     }
 
-    if arr.len() > 100 {
-        return Err(SlackCliError::InvalidBlocksJson(
-            "too many blocks (max 100)".to_string(),
-        ));
+    if arr.len() > max_blocks {
+        return Err(SlackCliError::InvalidBlocksJson(format!(
+            "too many blocks (max {max_blocks})"
+        )));
     }
 
     for item in arr {
@@ -75,37 +704,367 @@ fn parse_blocks_json(json_str: &str) -> Result<Vec<Value>, SlackCliError> {
         }
     }
 
+    Ok((arr.clone(), warning))
+}
+
+fn parse_attach_field(input: &str, short: bool) -> Result<AttachmentField, SlackCliError> {
+    let (title, value) = input
+        .split_once('=')
+        .ok_or_else(|| SlackCliError::InvalidAttachmentField(input.to_string()))?;
+
+    if title.trim().is_empty() {
+        return Err(SlackCliError::InvalidAttachmentField(input.to_string()));
+    }
+
+    Ok(AttachmentField {
+        title: title.trim().to_string(),
+        value: value.trim().to_string(),
+        short,
+    })
+}
+
+/// Parses a `--field "Key:Value"`, splitting on the first colon so values may contain more
+fn parse_section_field(input: &str) -> Result<SectionField, SlackCliError> {
+    match input.split_once(':') {
+        Some((key, value)) if !key.trim().is_empty() => Ok(SectionField {
+            key: key.trim().to_string(),
+            value: value.trim().to_string(),
+        }),
+        _ => Err(SlackCliError::InvalidSectionField(input.to_string())),
+    }
+}
+
+/// Most attachments chat.postMessage accepts before rejecting the request
+/// (`too_many_attachments`). Slack only renders the first `DEFAULT_MAX_ATTACHMENTS`
+/// (20), the default --max-attachments; no --max-attachments setting gets past this.
+const ATTACHMENTS_JSON_MAX: usize = 100;
+
+/// Accepts a non-empty JSON array of attachment objects, passed through as-is.
+fn parse_attachments_json(json_str: &str) -> Result<Vec<Value>, SlackCliError> {
+    let invalid = |msg: &str| SlackCliError::InvalidAttachmentsJson(msg.to_string());
+    let value: Value = serde_json::from_str(json_str).map_err(|e| invalid(&e.to_string()))?;
+    let arr = value
+        .as_array()
+        .ok_or_else(|| invalid("expected a JSON array"))?;
+    if arr.is_empty() {
+        return Err(invalid("attachments array is empty"));
+    }
+    if arr.len() > ATTACHMENTS_JSON_MAX {
+        return Err(invalid(&format!(
+            "too many attachments (max {ATTACHMENTS_JSON_MAX})"
+        )));
+    }
+    if !arr.iter().all(Value::is_object) {
+        return Err(invalid("each attachment must be a JSON object"));
+    }
     Ok(arr.clone())
 }
 
-fn read_blocks(source: &str) -> Result<Vec<Value>, SlackCliError> {
-    let json_str = if source == "-" {
+/// Reads a JSON file, or stdin for `-`; `invalid` wraps read failures
+fn read_json_source(
+    source: &str,
+    invalid: fn(String) -> SlackCliError,
+) -> Result<String, SlackCliError> {
+    if source == "-" {
         if io::stdin().is_terminal() {
-            return Err(SlackCliError::InvalidBlocksJson(
-                "no input piped to stdin".to_string(),
-            ));
+            return Err(invalid("no input piped to stdin".to_string()));
         }
         let mut buffer = String::new();
         io::stdin()
             .read_to_string(&mut buffer)
             .map_err(SlackCliError::StdinError)?;
-        buffer
+        Ok(buffer)
     } else {
-        std::fs::read_to_string(source).map_err(|e| {
-            SlackCliError::InvalidBlocksJson(format!("failed to read file '{source}': {e}"))
-        })?
+        std::fs::read_to_string(source)
+            .map_err(|e| invalid(format!("failed to read file '{source}': {e}")))
+    }
+}
+
+fn read_blocks(source: &str, max_blocks: usize) -> Result<Vec<Value>, SlackCliError> {
+    let json_str = read_json_source(source, SlackCliError::InvalidBlocksJson)?;
+    let (blocks, warning) = parse_blocks_json(&json_str, max_blocks)?;
+    if let Some(warning) = warning {
+        eprintln!("Warning: {warning}");
+    }
+    check_blocks(blocks)
+}
+
+fn read_blocks_from_url(
+    client: &HttpSlackClient,
+    url: &str,
+    max_blocks: usize,
+) -> Result<Vec<Value>, SlackCliError> {
+    let body = client.fetch_blocks_json(url)?;
+    let (blocks, warning) = parse_blocks_json(&body, max_blocks).map_err(|e| match e {
+        SlackCliError::InvalidBlocksJson(msg) => {
+            SlackCliError::InvalidBlocksJson(format!("response from '{url}': {msg}"))
+        }
+        other => other,
+    })?;
+    if let Some(warning) = warning {
+        eprintln!("Warning: {url}: {warning}");
+    }
+    check_blocks(blocks)
+}
+
+/// Rejects blocks that only work in modals and App Home, unless --allow-input-blocks
+#[cfg(feature = "block-validation")]
+fn check_input_blocks(args: &Args, blocks: Option<&[Value]>) -> Result<(), SlackCliError> {
+    match blocks {
+        Some(blocks) if !args.allow_input_blocks => {
+            slack_cli::validate::check_message_surface(blocks)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn check_blocks(blocks: Vec<Value>) -> Result<Vec<Value>, SlackCliError> {
+    #[cfg(feature = "block-validation")]
+    slack_cli::validate::check_unique_action_ids(&blocks)?;
+
+    Ok(blocks)
+}
+
+/// Fills in what the command line left out, from the environment and then the config
+/// file, and builds where the token is read from; flags always win
+fn merge_config(
+    args: &mut Args,
+    config: &CliConfig,
+    defaults: &Defaults,
+    home: &str,
+) -> Result<TokenConfig, SlackCliError> {
+    if args.channel.is_empty() && args.dm.is_empty() {
+        args.channel.extend(config.channel.clone());
+    }
+    // A --run command titles its own report
+    if args.title.is_none() && args.run.is_none() {
+        args.title = config.title.clone();
+    }
+    args.timeout_secs = args
+        .timeout_secs
+        .or(defaults.timeout_secs)
+        .or(config.timeout);
+
+    // --token-command overrides SLACK_TOKEN_COMMAND
+    let mut token_config = match args.profile {
+        Some(ref name) => TokenConfig::for_profile(name)?,
+        None => TokenConfig::default(),
     };
+    // A --profile picks its own files; otherwise the config file may name one
+    if let (None, Some(path)) = (&args.profile, config.token_file_in(home)) {
+        token_config.file_paths = vec![path];
+    }
+    if let Some(ref command) = args.token_command {
+        token_config.command = Some(command.clone());
+    }
+    token_config.keyring_only = args.token_from_keyring;
+    Ok(token_config)
+}
 
-    parse_blocks_json(&json_str)
+/// --username, else the channel's config file username with its template rendered
+fn username_for(
+    username: Option<&str>,
+    channel_defaults: &ChannelDefaults,
+) -> Result<Option<String>, SlackCliError> {
+    match username {
+        Some(username) => Ok(Some(username.to_string())),
+        None => channel_defaults.rendered_username(&SystemHostname, |key| std::env::var(key).ok()),
+    }
 }
 
-fn run() -> Result<(), SlackCliError> {
-    let args = Args::parse();
+/// Prints a report as the single JSON object `--output json` promises on stdout
+fn print_report(report: &SendReport) {
+    match serde_json::to_string(report) {
+        Ok(json) => println!("{json}"),
+        Err(e) => eprintln!("Error: {e}"),
+    }
+}
+
+fn run(mut args: Args) -> Result<(), SlackCliError> {
+    // Without the `tracing` feature, the library's built-in logger prints instead
+    slack_cli::log::set_verbosity(args.verbose);
+    #[cfg(feature = "tracing")]
+    if args.verbose > 0 {
+        let level = match args.verbose {
+            1 => tracing::Level::DEBUG,
+            _ => tracing::Level::TRACE,
+        };
+        // Only fails if a subscriber is already installed, which never happens here
+        let _ = tracing::subscriber::set_global_default(verbose::StderrSubscriber::new(level));
+    }
+
+    if args.list_colors {
+        let swatches = use_swatches(
+            io::stdout().is_terminal(),
+            args.no_color_output,
+            std::env::var("NO_COLOR").ok().as_deref(),
+        );
+        print!("{}", format_color_list(swatches));
+        return Ok(());
+    }
+
+    // Precedence: flag > [channels] entry > global config value
+    let cli_config = match args.config {
+        Some(ref path) => CliConfig::load(path)?,
+        None => CliConfig::load_default()?,
+    };
+    let defaults = Defaults::from_env()?;
+    let token_config = merge_config(
+        &mut args,
+        &cli_config,
+        &defaults,
+        &std::env::var("HOME").unwrap_or_default(),
+    )?;
+
+    if let Some(url) = webhook_from_env(&args, std::env::var(WEBHOOK_URL_ENV).ok(), || {
+        resolve_token_with_warning(&token_config).is_ok()
+    }) {
+        eprintln!("Note: no Slack token found; posting through the {WEBHOOK_URL_ENV} webhook");
+        args.webhook_url = Some(url);
+    }
+
+    if args.token.is_some() {
+        eprintln!(
+            "Warning: --token is visible to other users in process listings; prefer SLACK_API_KEY or --token-command"
+        );
+    }
+    // --token wins over every configured source
+    let resolve_token = || match args.token {
+        Some(ref token) => inline_token(token),
+        None => {
+            let (token, warning) = resolve_token_with_warning(&token_config)?;
+            if let Some(warning) = warning {
+                eprintln!("Warning: {warning}");
+            }
+            Ok(token)
+        }
+    };
+
+    let http = HttpClientConfig {
+        timeout: args.timeout_secs.map(Duration::from_secs),
+        connect_timeout: args.connect_timeout_secs.map(Duration::from_secs),
+        proxy: args.proxy.clone(),
+    };
+    // One client for the whole run, so every request shares its connection pool
+    let mut client = HttpSlackClient::new(http);
+    client.retry = retry_policy(&args, &defaults);
+    let (unfurl_links, unfurl_media) = unfurl_settings(&args, &defaults);
+    if let Some(ref base) = defaults.api_base {
+        client.base_url = base.clone();
+    }
+
+    if args.list_conversations {
+        let token = resolve_token()?;
+        let conversations = list_all_conversations(&client, &token, &args.resolve_types)?;
+        print!("{}", format_conversations(&conversations, args.output)?);
+        return Ok(());
+    }
+
+    let channel_suffix = match args.channel_suffix_env {
+        Some(ref var) => Some(std::env::var(var).map_err(|_| {
+            SlackCliError::InvalidConfig(format!("--channel-suffix-env: {var} is not set"))
+        })?),
+        None => args.channel_suffix.clone(),
+    };
+    if args.channel.is_empty() && args.dm.is_empty() && args.webhook_url.is_none() {
+        return Err(SlackCliError::InvalidConfig(
+            "no channel: pass --channel or --dm, or set `channel` in the config file".to_string(),
+        ));
+    }
+    check_single_channel_flags(&args)?;
+    // --dm users stand in until their DM is opened; a webhook posts to the
+    // channel it was created for
+    let channel = args
+        .channel
+        .first()
+        .map(|channel| {
+            decorate_channel(
+                channel,
+                args.channel_prefix.as_deref(),
+                channel_suffix.as_deref(),
+            )
+        })
+        .unwrap_or_else(|| args.dm.join(","));
+
+    if args.print_channel_id {
+        let token = resolve_token()?;
+        let id = ChannelResolver::new(&args.resolve_types).resolve(&client, &token, &channel)?;
+        println!("{id}");
+        return Ok(());
+    }
+
+    if let Some(post_at) = args.schedule_at {
+        validate_schedule_time(post_at, SystemClock.now())?;
+    }
+
+    if let Some(ref ts) = args.delete_ts {
+        let token = resolve_token()?;
+        delete_message(&client, &token, &channel, ts)?;
+        if args.output == OutputFormat::Json {
+            print_report(&SendReport {
+                ok: true,
+                channel: Some(channel),
+                ts: Some(ts.clone()),
+                ..SendReport::default()
+            });
+        }
+        return Ok(());
+    }
 
-    let (message, blocks) = if let Some(source) = args.blocks {
-        let blocks = read_blocks(&source)?;
-        let message = args.message.unwrap_or_default();
-        (message, Some(blocks))
+    let raw_blocks = if let Some(ref url) = args.blocks_from_url {
+        Some(read_blocks_from_url(&client, url, args.max_blocks)?)
+    } else if let Some(ref source) = args.blocks {
+        Some(read_blocks(source, args.max_blocks)?)
+    } else {
+        None
+    };
+
+    if args.blocks.as_deref() == Some("-") && args.attachments.as_deref() == Some("-") {
+        return Err(SlackCliError::InvalidConfig(
+            "--blocks and --attachments can't both read from stdin".to_string(),
+        ));
+    }
+    let raw_attachments = args
+        .attachments
+        .as_deref()
+        .map(|source| {
+            parse_attachments_json(&read_json_source(
+                source,
+                SlackCliError::InvalidAttachmentsJson,
+            )?)
+        })
+        .transpose()?;
+
+    #[cfg(feature = "block-validation")]
+    if args.schema_validate {
+        let blocks = raw_blocks.ok_or_else(|| {
+            SlackCliError::InvalidConfig(
+                "--schema-validate needs --blocks or --blocks-from-url".to_string(),
+            )
+        })?;
+        let errors = slack_cli::validate::validate_schema(&blocks);
+        if errors.is_empty() {
+            println!("{} blocks valid", blocks.len());
+            return Ok(());
+        }
+        for error in &errors {
+            eprintln!("{error}");
+        }
+        return Err(SlackCliError::InvalidBlocksJson(format!(
+            "{} schema error(s)",
+            errors.len()
+        )));
+    }
+
+    #[cfg(feature = "block-validation")]
+    check_input_blocks(&args, raw_blocks.as_deref())?;
+
+    let report = args.run.as_deref().map(run_command).transpose()?;
+
+    let (message, blocks) = if let Some(ref report) = report {
+        (report.message(args.run_code_block), None)
+    } else if raw_blocks.is_some() || raw_attachments.is_some() {
+        (args.message.unwrap_or_default(), raw_blocks)
     } else {
         let message = match args.message {
             Some(msg) if !msg.trim().is_empty() => msg,
@@ -114,36 +1073,408 @@ fn run() -> Result<(), SlackCliError> {
                 if io::stdin().is_terminal() {
                     return Err(SlackCliError::NoMessage);
                 }
-                read_stdin()?
+                read_stdin(args.input_encoding.as_deref(), args.on_invalid)?
             }
         };
         (message, None)
     };
 
-    let token = resolve_token()?;
+    let message = if args.strip_ansi {
+        strip_ansi(&message)
+    } else {
+        message
+    };
+
+    let message = if args.collapse_repeats {
+        collapse_repeats(&message, args.collapse_min)
+    } else {
+        message
+    };
+
+    let expansion = EmojiExpansion {
+        title: args.expand_emoji_in_title,
+        body: args.expand_emoji_in_body,
+    };
+    let title = args
+        .title
+        .or_else(|| report.as_ref().map(CommandReport::title));
+    let (title, message) = expansion.apply(title, message);
+
+    let attachment_fields = args
+        .attach_fields
+        .iter()
+        .map(|f| parse_attach_field(f, args.attach_field_short))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let section_fields = args
+        .section_fields
+        .iter()
+        .map(|f| parse_section_field(f))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut context = args.context_lines.clone();
+    if let Some(ref since) = args.since {
+        context.push(since_footer(since, &SystemClock)?);
+    }
+
+    let message = if args.tag_hostname {
+        let placement = if args.hostname_in_footer {
+            HostnamePlacement::Footer
+        } else {
+            HostnamePlacement::Prefix
+        };
+        let hostname = resolve_hostname(&SystemHostname);
+        tag_hostname(message, &mut context, &hostname, placement)
+    } else {
+        message
+    };
+
+    let channel_defaults = cli_config.for_channel(&channel);
+    // Precedence: --color or --color-by > --run exit status > config file
+    let derived_color = args
+        .color_by
+        .as_deref()
+        .map(color_for_source)
+        .or(report.as_ref().map(CommandReport::color))
+        .map(str::to_string);
+    // Updates can't carry a color, so --run and config file colors are dropped
+    let color = if args.update_ts.is_some() {
+        None
+    } else {
+        args.color
+            .clone()
+            .or(derived_color.clone())
+            .or(channel_defaults.color.clone())
+    };
+    let username = username_for(args.username.as_deref(), &channel_defaults)?;
+
+    // A dry run never talks to Slack and a webhook URL is its own credential
+    let token = if args.dry_run || args.webhook_url.is_some() {
+        String::new()
+    } else {
+        resolve_token()?
+    };
+
+    // History lookups need the channel ID rather than "#name". One resolver lists
+    // conversations at most once, however many channels are posted to.
+    let resolver = ChannelResolver::new(&args.resolve_types);
+    let resolve = (args.correlation_key.is_some() || args.resolve_channel) && !args.dry_run;
+    let channel = if !args.dm.is_empty() && !args.dry_run {
+        open_dm(&client, &token, &args.dm)?
+    } else if resolve {
+        resolver.resolve(&client, &token, &channel)?
+    } else {
+        channel
+    };
 
     let config = SendConfig {
-        channel: args.channel,
+        channel,
         message,
-        color: args.color,
-        title: args.title,
+        color,
+        title,
         token,
         blocks,
+        attachments: raw_attachments,
+        attachment_fields,
+        context,
+        force_shape: if args.force_blocks {
+            Some(PayloadShape::Blocks)
+        } else if args.force_attachment {
+            Some(PayloadShape::Attachment)
+        } else {
+            None
+        },
+        unfurl_links,
+        unfurl_media,
+        team_id: args.team_id,
+        split_strategy: args.split_strategy,
+        attachment_fallback: args.attachment_fallback,
+        no_fallback_text: args.no_fallback_text,
+        fallback_text: args.fallback_text.clone(),
+        truncate_title: args.truncate_title,
+        clean_title: args.clean_title,
+        divider: args.divider,
+        footer: args.footer.clone(),
+        footer_icon: args.footer_icon.clone(),
+        pretext: args.pretext.clone(),
+        attachment_ts: args.attachment_ts,
+        author_name: args.author_name.clone(),
+        author_link: args.author_link.clone(),
+        author_icon: args.author_icon.clone(),
+        section_fields,
+        image_url: args.image_url.clone(),
+        image_alt: args.image_alt.clone(),
+        correlation_key: args.correlation_key,
+        thread_ts: args.thread_ts,
+        reply_broadcast: args.reply_broadcast,
+        react: args.react.clone(),
+        pin: args.pin,
+        username,
+        icon_emoji: args.icon_emoji.clone(),
+        icon_url: args.icon_url.clone(),
+        max_attachments: Some(args.max_attachments),
+        attachment_overflow: args.on_too_many_attachments,
+        update_ts: args.update_ts.clone(),
+        ephemeral_user: args.ephemeral_user.clone(),
+        schedule_at: args.schedule_at,
     };
 
-    let client = HttpSlackClient;
-    let result = send_message(&client, &config)?;
+    // Extra channels share the message but keep their own config file defaults
+    let also_configs: Vec<SendConfig> = args
+        .channel
+        .iter()
+        .skip(1)
+        .chain(&args.also_channels)
+        .map(|also| {
+            let channel = decorate_channel(
+                also,
+                args.channel_prefix.as_deref(),
+                channel_suffix.as_deref(),
+            );
+            let channel_defaults = cli_config.for_channel(&channel);
+            let username = username_for(args.username.as_deref(), &channel_defaults)?;
+            let channel = if resolve {
+                resolver.resolve(&client, &config.token, &channel)?
+            } else {
+                channel
+            };
+            Ok(SendConfig {
+                color: args
+                    .color
+                    .clone()
+                    .or(derived_color.clone())
+                    .or(channel_defaults.color.clone()),
+                username,
+                channel,
+                ..config.clone()
+            })
+        })
+        .collect::<Result<_, SlackCliError>>()?;
 
+    if args.print_curl {
+        for config in std::iter::once(&config).chain(&also_configs) {
+            let plan = plan_send(config)?;
+            let body = serde_json::to_vec(&plan.payload)
+                .map_err(|e| SlackCliError::SerializationError(e.to_string()))?;
+            eprintln!("{}", curl_command(&client.base_url, plan.endpoint, &body));
+        }
+    }
+
+    if args.dry_run {
+        let plans = std::iter::once(&config)
+            .chain(&also_configs)
+            .map(plan_send)
+            .collect::<Result<Vec<_>, _>>()?;
+        match args.output {
+            // A single plan stays an object; a fan-out prints an array of them
+            OutputFormat::Json => {
+                let json = if plans.len() == 1 {
+                    serde_json::to_string_pretty(&plans[0])
+                } else {
+                    serde_json::to_string_pretty(&plans)
+                };
+                println!(
+                    "{}",
+                    json.map_err(|e| SlackCliError::SerializationError(e.to_string()))?
+                );
+            }
+            OutputFormat::Text => {
+                for plan in &plans {
+                    if let Some(ref warning) = plan.warning {
+                        eprintln!("Warning: {warning}");
+                    }
+                    match args.webhook_url {
+                        Some(_) => println!("Would post to the incoming webhook:"),
+                        None => println!("Would post to {} via {}:", plan.channel, plan.endpoint),
+                    }
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(&plan.payload)
+                            .map_err(|e| SlackCliError::SerializationError(e.to_string()))?
+                    );
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    // A fan-out reports per channel on stderr, and a webhook posts wherever it was set up for
+    let report_channel =
+        (also_configs.is_empty() && !config.channel.is_empty()).then(|| config.channel.clone());
+
+    let hash = content_hash(&config);
+    if let Some(ref path) = args.state_file {
+        let previous = match read_state(path) {
+            Ok(loaded) => {
+                if let Some(warning) = loaded.warning {
+                    eprintln!("Warning: {warning}");
+                }
+                loaded.value
+            }
+            Err(e) => {
+                eprintln!("Warning: ignoring unreadable state: {e}");
+                None
+            }
+        };
+        if args.only_on_change && !should_send(previous.as_ref(), hash) {
+            if args.output == OutputFormat::Json {
+                print_report(&SendReport::skipped(
+                    report_channel,
+                    "unchanged since the last successful send; not sending",
+                ));
+            } else {
+                eprintln!("Unchanged since the last successful send; not sending");
+            }
+            return Ok(());
+        }
+    }
+
+    let stats = args.stats || args.verbose > 0;
+
+    let sent = if let Some(ref url) = args.webhook_url {
+        send_message(&WebhookSlackClient::new(url, client), &config)
+    } else if !also_configs.is_empty() {
+        let configs: Vec<SendConfig> = std::iter::once(config.clone())
+            .chain(also_configs)
+            .collect();
+        let outcomes = fan_out(
+            &client,
+            &configs,
+            args.concurrency,
+            args.fail_fast,
+            &SystemClock,
+        );
+        eprint!("{}", format_summary(&outcomes, stats));
+        let total = outcomes.len();
+        let failed = outcomes.iter().filter(|o| o.result.is_err()).count();
+        if failed == 0 {
+            Ok(SendResult {
+                ok: true,
+                warning: outcomes.into_iter().find_map(|o| o.result.ok()?.warning),
+                ts: None,
+                scheduled_message_id: None,
+            })
+        } else {
+            Err(SlackCliError::FanOutFailed { failed, total })
+        }
+    } else {
+        match args.mirror_webhook {
+            Some(ref url) => {
+                let report = send_mirrored(&client, &client, url, &config)?;
+                match report.primary {
+                    Ok(_) => eprintln!("Channel {}: sent", config.channel),
+                    Err(ref e) => eprintln!("Channel {}: failed: {e}", config.channel),
+                }
+                match report.mirror {
+                    Ok(()) => eprintln!("Webhook: sent"),
+                    Err(ref e) => eprintln!("Webhook: failed: {e}"),
+                }
+                report.primary
+            }
+            None => match args.summary_lines {
+                Some(lines) => send_summarized(&client, &config, lines),
+                None => send_message(&client, &config),
+            },
+        }
+    };
+
+    if let Some(ref path) = args.state_file {
+        let outcome = if sent.is_ok() {
+            Outcome::Success
+        } else {
+            Outcome::Failure
+        };
+        let state = SendState::new(outcome, hash, SystemClock.now());
+        if let Err(e) = write_state(path, &state) {
+            eprintln!("Warning: {e}");
+        }
+    }
+
+    let result = sent?;
+
+    if args.output == OutputFormat::Json {
+        print_report(&SendReport::sent(report_channel, result));
+        return Ok(());
+    }
     if let Some(warning) = result.warning {
         eprintln!("Warning: {warning}");
     }
+    if let Some(id) = result.scheduled_message_id {
+        println!("{id}");
+    }
 
     Ok(())
 }
 
+#[cfg(feature = "tracing")]
+mod verbose {
+    use std::fmt::{self, Write};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Level, Metadata, Subscriber};
+
+    /// Prints this crate's events up to `max_level` as "[LEVEL] message key=value ..."
+    pub struct StderrSubscriber {
+        max_level: Level,
+        next_id: AtomicU64,
+    }
+
+    impl StderrSubscriber {
+        pub fn new(max_level: Level) -> Self {
+            StderrSubscriber {
+                max_level,
+                next_id: AtomicU64::new(0),
+            }
+        }
+    }
+
+    struct LineVisitor(String);
+
+    impl Visit for LineVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            if field.name() == "message" {
+                let _ = write!(self.0, "{value:?}");
+            } else {
+                let _ = write!(self.0, " {}={value:?}", field.name());
+            }
+        }
+    }
+
+    impl Subscriber for StderrSubscriber {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            // HTTP library internals stay out: their trace output can carry request headers
+            *metadata.level() <= self.max_level && metadata.target().starts_with("slack_cli")
+        }
+
+        fn new_span(&self, _span: &Attributes<'_>) -> Id {
+            Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut visitor = LineVisitor(String::new());
+            event.record(&mut visitor);
+            eprintln!("[{}] {}", event.metadata().level(), visitor.0);
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+}
+
 fn main() {
-    if let Err(e) = run() {
-        eprintln!("Error: {e}");
+    let args = Args::parse();
+    let output = args.output;
+    if let Err(e) = run(args) {
+        match output {
+            OutputFormat::Json => print_report(&SendReport::failed(&e)),
+            OutputFormat::Text => eprintln!("Error: {e}"),
+        }
         process::exit(1);
     }
 }
@@ -152,18 +1483,421 @@ fn main() {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_retry_flags_override_profile() {
+        let args = Args::try_parse_from([
+            "slack-cli",
+            "--channel",
+            "#ops",
+            "--retry-profile",
+            "aggressive",
+            "--max-retries",
+            "1",
+            "--max-retry-delay",
+            "5",
+        ])
+        .unwrap();
+        let policy = retry_policy(&args, &Defaults::default());
+        assert_eq!(policy.max_retries, 1);
+        assert_eq!(policy.max_delay, Some(Duration::from_secs(5)));
+        // Untouched profile values survive
+        assert!(policy.retry_server_errors);
+        assert_eq!(policy.jitter, Some(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn test_channel_accepts_list_and_repeats() {
+        let args =
+            Args::try_parse_from(["slack-cli", "-c", "#ops,#oncall", "-c", "@alice"]).unwrap();
+        assert_eq!(args.channel, vec!["#ops", "#oncall", "@alice"]);
+        assert!(check_single_channel_flags(&args).is_ok());
+    }
+
+    #[test]
+    fn test_several_channels_reject_single_channel_flags() {
+        let args = Args::try_parse_from([
+            "slack-cli",
+            "-c",
+            "C0123,C0456",
+            "--thread-ts",
+            "1700000000.000100",
+        ])
+        .unwrap();
+        assert!(matches!(
+            check_single_channel_flags(&args),
+            Err(SlackCliError::InvalidConfig(ref msg)) if msg.contains("--thread-ts")
+        ));
+
+        let single = Args::try_parse_from([
+            "slack-cli",
+            "-c",
+            "C0123",
+            "--thread-ts",
+            "1700000000.000100",
+        ])
+        .unwrap();
+        assert!(check_single_channel_flags(&single).is_ok());
+    }
+
+    #[test]
+    fn test_channel_suffix_accepts_leading_hyphen() {
+        let args = Args::try_parse_from([
+            "slack-cli",
+            "-c",
+            "#alerts",
+            "--channel-suffix",
+            "-prod",
+            "-m",
+            "hi",
+        ])
+        .unwrap();
+        assert_eq!(args.channel_suffix.as_deref(), Some("-prod"));
+        assert_eq!(args.message.as_deref(), Some("hi"));
+        assert_eq!(
+            decorate_channel(
+                &args.channel[0],
+                args.channel_prefix.as_deref(),
+                args.channel_suffix.as_deref()
+            ),
+            "#alerts-prod"
+        );
+    }
+
+    #[test]
+    fn test_no_fallback_text_does_not_need_color() {
+        let args =
+            Args::try_parse_from(["slack-cli", "-c", "C0123", "-m", "hi", "--no-fallback-text"])
+                .unwrap();
+        assert!(args.no_fallback_text && args.color.is_none());
+    }
+
+    #[test]
+    fn test_malformed_ts_names_its_flag() {
+        for flag in ["--thread-ts", "--update-ts", "--delete-ts"] {
+            let err = Args::try_parse_from(["slack-cli", "-c", "C0123", flag, "yesterday"])
+                .err()
+                .unwrap();
+            assert!(
+                err.to_string()
+                    .contains(&format!("{flag}: invalid Slack timestamp")),
+                "{err}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_webhook_env_var_only_applies_without_a_token() {
+        let env_url = || Some("https://hooks.example/env".to_string());
+        let parse =
+            |flags: &[&str]| Args::try_parse_from(["slack-cli"].iter().chain(flags)).unwrap();
+
+        let args = parse(&["-m", "hi"]);
+        assert_eq!(
+            webhook_from_env(&args, env_url(), || false).as_deref(),
+            Some("https://hooks.example/env")
+        );
+        // A configured token keeps the Web API
+        assert_eq!(webhook_from_env(&args, env_url(), || true), None);
+        let args = parse(&["-c", "#ops", "-m", "hi", "--token", "xoxb-inline"]);
+        assert_eq!(webhook_from_env(&args, env_url(), || false), None);
+        // Unset or empty, there is nothing to fall back to
+        assert_eq!(webhook_from_env(&args, None, || false), None);
+        assert_eq!(
+            webhook_from_env(&parse(&["-m", "hi"]), Some(" ".to_string()), || false),
+            None
+        );
+    }
+
+    #[test]
+    fn test_webhook_env_var_leaves_web_api_modes_alone() {
+        let env_url = || Some("https://hooks.example/env".to_string());
+        for flags in [
+            &["-c", "C1", "--delete-ts", "1700000000.000100"][..],
+            &["--list-conversations"],
+            &["-c", "#ops", "--print-channel-id"],
+            &["-c", "C1", "-m", "hi", "--update-ts", "1700000000.000100"],
+            &["-c", "#ops,#dev", "-m", "hi"],
+            &["--dm", "U0123", "-m", "hi"],
+        ] {
+            let args = Args::try_parse_from(["slack-cli"].iter().chain(flags)).unwrap();
+            assert_eq!(
+                webhook_from_env(&args, env_url(), || false),
+                None,
+                "{flags:?}"
+            );
+        }
+
+        // The explicit flag wins and is used as given
+        let args = Args::try_parse_from([
+            "slack-cli",
+            "-m",
+            "hi",
+            "--webhook-url",
+            "https://hooks.example/flag",
+        ])
+        .unwrap();
+        assert_eq!(webhook_from_env(&args, env_url(), || false), None);
+        assert_eq!(
+            args.webhook_url.as_deref(),
+            Some("https://hooks.example/flag")
+        );
+    }
+
+    #[test]
+    fn test_webhook_url_makes_channel_optional() {
+        let args = Args::try_parse_from(["slack-cli", "--webhook-url", "https://hooks.example/x"])
+            .unwrap();
+        assert!(args.channel.is_empty());
+        assert!(Args::try_parse_from([
+            "slack-cli",
+            "--webhook-url",
+            "https://hooks.example/x",
+            "--delete-ts",
+            "1700000000.000100",
+        ])
+        .is_err());
+
+        let args = Args::try_parse_from([
+            "slack-cli",
+            "-c",
+            "#ops,#dev",
+            "--webhook-url",
+            "https://hooks.example/x",
+        ])
+        .unwrap();
+        assert!(matches!(
+            check_single_channel_flags(&args),
+            Err(SlackCliError::InvalidConfig(ref msg)) if msg.contains("--webhook-url")
+        ));
+    }
+
+    #[test]
+    fn test_resolve_channel_needs_the_web_api() {
+        let args = Args::try_parse_from(["slack-cli", "-c", "#ops", "--resolve-channel"]).unwrap();
+        assert!(args.resolve_channel);
+        assert!(Args::try_parse_from([
+            "slack-cli",
+            "--resolve-channel",
+            "--webhook-url",
+            "https://hooks.example/x",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_dm_takes_users_instead_of_a_channel() {
+        let args = Args::try_parse_from(["slack-cli", "--dm", "U012345,U067890"]).unwrap();
+        assert_eq!(args.dm, ["U012345", "U067890"]);
+        assert!(args.channel.is_empty());
+        assert!(Args::try_parse_from(["slack-cli", "--dm", "U012345", "-c", "#ops"]).is_err());
+    }
+
+    #[test]
+    fn test_inline_token() {
+        assert_eq!(inline_token(" xoxb-inline \n").unwrap(), "xoxb-inline");
+        assert!(matches!(
+            inline_token("  "),
+            Err(SlackCliError::InvalidInput(ref msg)) if msg.contains("--token")
+        ));
+        assert!(Args::try_parse_from([
+            "slack-cli",
+            "-c",
+            "#ops",
+            "--token",
+            "xoxb-inline",
+            "--token-command",
+            "pass slack",
+        ])
+        .is_err());
+    }
+
+    /// Parses `flags` and merges `config` and `defaults` into them
+    fn merged(flags: &[&str], config: &CliConfig, defaults: &Defaults) -> (Args, TokenConfig) {
+        let mut args = Args::try_parse_from(["slack-cli"].iter().chain(flags)).unwrap();
+        let token_config = merge_config(&mut args, config, defaults, "/home/ci").unwrap();
+        (args, token_config)
+    }
+
+    #[test]
+    fn test_config_file_fills_missing_channel() {
+        let config = CliConfig::parse(r##"channel = "#deploys""##).unwrap();
+        let defaults = Defaults::default();
+
+        let (args, _) = merged(&[], &config, &defaults);
+        assert_eq!(args.channel, vec!["#deploys"]);
+
+        let (args, _) = merged(&["-c", "#ops"], &config, &defaults);
+        assert_eq!(args.channel, vec!["#ops"]);
+
+        let (args, _) = merged(&[], &CliConfig::default(), &defaults);
+        assert!(args.channel.is_empty());
+    }
+
+    #[test]
+    fn test_title_precedence() {
+        let config = CliConfig::parse(r#"title = "From config""#).unwrap();
+        let defaults = Defaults::default();
+
+        let (args, _) = merged(&["--title", "From flag"], &config, &defaults);
+        assert_eq!(args.title.as_deref(), Some("From flag"));
+
+        let (args, _) = merged(&[], &config, &defaults);
+        assert_eq!(args.title.as_deref(), Some("From config"));
+
+        // The command report supplies the title instead
+        let (args, _) = merged(&["--run", "true"], &config, &defaults);
+        assert_eq!(args.title, None);
+    }
+
+    #[test]
+    fn test_timeout_precedence() {
+        let config = CliConfig::parse("timeout = 10").unwrap();
+        let env = Defaults {
+            timeout_secs: Some(20),
+            ..Defaults::default()
+        };
+
+        let (args, _) = merged(&["--timeout", "30"], &config, &env);
+        assert_eq!(args.timeout_secs, Some(30));
+
+        let (args, _) = merged(&[], &config, &env);
+        assert_eq!(args.timeout_secs, Some(20));
+
+        let (args, _) = merged(&[], &config, &Defaults::default());
+        assert_eq!(args.timeout_secs, Some(10));
+
+        let (args, _) = merged(&[], &CliConfig::default(), &Defaults::default());
+        assert_eq!(args.timeout_secs, None);
+    }
+
+    #[test]
+    fn test_token_file_precedence() {
+        let config = CliConfig::parse(r#"token_file = "~/.secrets/slack""#).unwrap();
+        let defaults = Defaults::default();
+
+        // A --profile reads its own files rather than the config file's
+        let (_, token_config) = merged(&["--profile", "acme"], &config, &defaults);
+        assert!(token_config.file_paths[0].ends_with(".slack/acme/api-token"));
+
+        let (_, token_config) = merged(&[], &config, &defaults);
+        assert_eq!(
+            token_config.file_paths,
+            [PathBuf::from("/home/ci/.secrets/slack")]
+        );
+        // The environment variable is read before any file
+        assert_eq!(token_config.env_var, "SLACK_API_KEY");
+
+        let (_, token_config) = merged(&[], &CliConfig::default(), &defaults);
+        assert_eq!(token_config.file_paths, TokenConfig::default().file_paths);
+    }
+
+    #[cfg(feature = "block-validation")]
+    #[test]
+    fn test_allow_input_blocks_bypasses_surface_check() {
+        let blocks: Vec<Value> = serde_json::from_str(
+            r#"[{"type": "input", "label": {"type": "plain_text", "text": "Name"},
+                 "element": {"type": "plain_text_input"}}]"#,
+        )
+        .unwrap();
+
+        let args = Args::try_parse_from(["slack-cli", "-c", "#ops"]).unwrap();
+        assert!(matches!(
+            check_input_blocks(&args, Some(&blocks)),
+            Err(SlackCliError::InvalidBlocksJson(ref msg)) if msg.contains("--allow-input-blocks")
+        ));
+
+        let args =
+            Args::try_parse_from(["slack-cli", "-c", "#ops", "--allow-input-blocks"]).unwrap();
+        assert!(check_input_blocks(&args, Some(&blocks)).is_ok());
+    }
+
+    #[test]
+    fn test_verbose_is_repeatable() {
+        let verbosity = |flags: &[&str]| {
+            Args::try_parse_from(["slack-cli", "-c", "#ops"].iter().chain(flags))
+                .unwrap()
+                .verbose
+        };
+        assert_eq!(verbosity(&[]), 0);
+        assert_eq!(verbosity(&["-v"]), 1);
+        assert_eq!(verbosity(&["-vv"]), 2);
+        assert_eq!(verbosity(&["--verbose", "--verbose"]), 2);
+    }
+
+    #[test]
+    fn test_unfurl_flags() {
+        let parse = |flags: &[&str]| {
+            let args =
+                Args::try_parse_from(["slack-cli", "-c", "#ops"].iter().chain(flags)).unwrap();
+            unfurl_settings(&args, &Defaults::default())
+        };
+        assert_eq!(parse(&[]), (None, None));
+        assert_eq!(parse(&["--no-unfurl"]), (Some(false), Some(false)));
+        assert_eq!(parse(&["--unfurl"]), (Some(true), Some(true)));
+        assert_eq!(parse(&["--unfurl-media"]), (None, Some(true)));
+
+        let disabled = Defaults {
+            unfurl_links: Some(false),
+            unfurl_media: Some(false),
+            ..Defaults::default()
+        };
+        let args = Args::try_parse_from(["slack-cli", "-c", "#ops", "--unfurl"]).unwrap();
+        assert_eq!(unfurl_settings(&args, &disabled), (Some(true), Some(true)));
+
+        assert!(
+            Args::try_parse_from(["slack-cli", "-c", "#ops", "--no-unfurl", "--unfurl"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_retry_defaults_without_profile() {
+        let args = Args::try_parse_from(["slack-cli", "--channel", "#ops"]).unwrap();
+        let policy = retry_policy(&args, &Defaults::default());
+        assert_eq!(policy.max_retries, slack_cli::retry::DEFAULT_MAX_RETRIES);
+        assert!(!policy.retry_server_errors);
+    }
+
     #[test]
     fn test_parse_blocks_json_valid_array() {
         let json = r#"[{"type": "section", "text": {"type": "mrkdwn", "text": "Hello"}}]"#;
-        let result = parse_blocks_json(json).unwrap();
+        let result = parse_blocks_json(json, DEFAULT_MAX_BLOCKS).unwrap().0;
         assert_eq!(result.len(), 1);
         assert_eq!(result[0]["type"], "section");
     }
 
+    #[test]
+    fn test_parse_attachments_json() {
+        let json = r##"[{"color": "#36a64f", "text": "ok"}, {"text": "second"}]"##;
+        let attachments = parse_attachments_json(json).unwrap();
+        assert_eq!(attachments.len(), 2);
+        assert_eq!(attachments[0]["color"], "#36a64f");
+
+        for (json, expected) in [
+            ("[]", "empty"),
+            (r#"{"text": "x"}"#, "array"),
+            (r#"["x"]"#, "object"),
+            ("not json", "expected"),
+        ] {
+            assert!(
+                matches!(
+                    parse_attachments_json(json),
+                    Err(SlackCliError::InvalidAttachmentsJson(ref msg)) if msg.contains(expected)
+                ),
+                "{json}"
+            );
+        }
+
+        let many = serde_json::to_string(&vec![serde_json::json!({}); 101]).unwrap();
+        assert!(matches!(
+            parse_attachments_json(&many),
+            Err(SlackCliError::InvalidAttachmentsJson(ref msg)) if msg.contains("max 100")
+        ));
+    }
+
     #[test]
     fn test_parse_blocks_json_multiple_blocks() {
         let json = r#"[{"type": "section", "text": {"type": "mrkdwn", "text": "Hello"}}, {"type": "divider"}]"#;
-        let result = parse_blocks_json(json).unwrap();
+        let result = parse_blocks_json(json, DEFAULT_MAX_BLOCKS).unwrap().0;
         assert_eq!(result.len(), 2);
         assert_eq!(result[0]["type"], "section");
         assert_eq!(result[1]["type"], "divider");
@@ -171,7 +1905,7 @@ mod tests {
 
     #[test]
     fn test_parse_blocks_json_empty_array_rejected() {
-        let result = parse_blocks_json("[]");
+        let result = parse_blocks_json("[]", DEFAULT_MAX_BLOCKS);
         assert!(matches!(
             result,
             Err(SlackCliError::InvalidBlocksJson(ref msg)) if msg.contains("empty")
@@ -180,7 +1914,7 @@ mod tests {
 
     #[test]
     fn test_parse_blocks_json_non_array_rejected() {
-        let result = parse_blocks_json(r#"{"type": "section"}"#);
+        let result = parse_blocks_json(r#"{"type": "section"}"#, DEFAULT_MAX_BLOCKS);
         assert!(matches!(
             result,
             Err(SlackCliError::InvalidBlocksJson(ref msg)) if msg.contains("array")
@@ -189,7 +1923,7 @@ mod tests {
 
     #[test]
     fn test_parse_blocks_json_non_object_elements_rejected() {
-        let result = parse_blocks_json(r#"["not an object"]"#);
+        let result = parse_blocks_json(r#"["not an object"]"#, DEFAULT_MAX_BLOCKS);
         assert!(matches!(
             result,
             Err(SlackCliError::InvalidBlocksJson(ref msg)) if msg.contains("object")
@@ -198,7 +1932,7 @@ mod tests {
 
     #[test]
     fn test_parse_blocks_json_invalid_json_rejected() {
-        let result = parse_blocks_json("not json at all");
+        let result = parse_blocks_json("not json at all", DEFAULT_MAX_BLOCKS);
         assert!(matches!(result, Err(SlackCliError::InvalidBlocksJson(_))));
     }
 
@@ -208,7 +1942,7 @@ mod tests {
             .map(|_| serde_json::json!({"type": "divider"}))
             .collect();
         let json = serde_json::to_string(&blocks).unwrap();
-        let result = parse_blocks_json(&json);
+        let result = parse_blocks_json(&json, DEFAULT_MAX_BLOCKS);
         assert!(matches!(
             result,
             Err(SlackCliError::InvalidBlocksJson(ref msg)) if msg.contains("max 100")
@@ -221,7 +1955,122 @@ mod tests {
             .map(|_| serde_json::json!({"type": "divider"}))
             .collect();
         let json = serde_json::to_string(&blocks).unwrap();
-        let result = parse_blocks_json(&json).unwrap();
+        let result = parse_blocks_json(&json, DEFAULT_MAX_BLOCKS).unwrap().0;
         assert_eq!(result.len(), 100);
     }
+
+    #[test]
+    fn test_parse_blocks_json_custom_cap_below_default() {
+        let json = r#"[{"type": "divider"}, {"type": "divider"}, {"type": "divider"}]"#;
+        assert_eq!(parse_blocks_json(json, 3).unwrap().0.len(), 3);
+        assert!(matches!(
+            parse_blocks_json(json, 2),
+            Err(SlackCliError::InvalidBlocksJson(ref msg)) if msg.contains("max 2")
+        ));
+    }
+
+    #[test]
+    fn test_parse_blocks_json_custom_cap_above_default() {
+        let blocks: Vec<Value> = (0..150)
+            .map(|_| serde_json::json!({"type": "divider"}))
+            .collect();
+        let json = serde_json::to_string(&blocks).unwrap();
+        assert_eq!(parse_blocks_json(&json, 200).unwrap().0.len(), 150);
+    }
+
+    #[test]
+    fn test_parse_blocks_json_custom_cap_keeps_shape_checks() {
+        assert!(parse_blocks_json("[]", 500).is_err());
+        assert!(parse_blocks_json(r#"[1]"#, 500).is_err());
+    }
+
+    #[test]
+    fn test_parse_blocks_json_versioned_wrapper() {
+        let json = r#"{"version": "1", "blocks": [{"type": "divider"}]}"#;
+        let (blocks, warning) = parse_blocks_json(json, DEFAULT_MAX_BLOCKS).unwrap();
+        assert_eq!(blocks, vec![serde_json::json!({"type": "divider"})]);
+        assert!(warning.is_none());
+    }
+
+    #[test]
+    fn test_parse_blocks_json_version_mismatch_warns() {
+        let json = r#"{"version": 2, "blocks": [{"type": "divider"}]}"#;
+        let (blocks, warning) = parse_blocks_json(json, DEFAULT_MAX_BLOCKS).unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert!(warning.unwrap().contains("version '2' is not supported"));
+    }
+
+    #[test]
+    fn test_parse_blocks_json_wrapper_still_validates_blocks() {
+        let json = r#"{"version": "1", "blocks": []}"#;
+        assert!(matches!(
+            parse_blocks_json(json, DEFAULT_MAX_BLOCKS),
+            Err(SlackCliError::InvalidBlocksJson(ref msg)) if msg.contains("empty")
+        ));
+        let json = r#"{"version": "1", "blocks": {"type": "divider"}}"#;
+        assert!(parse_blocks_json(json, DEFAULT_MAX_BLOCKS).is_err());
+    }
+
+    #[test]
+    fn test_parse_blocks_json_plain_array_has_no_warning() {
+        let json = r#"[{"type": "divider"}]"#;
+        let (blocks, warning) = parse_blocks_json(json, DEFAULT_MAX_BLOCKS).unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert!(warning.is_none());
+    }
+
+    #[test]
+    fn test_parse_section_field() {
+        let field = parse_section_field("Uptime: 12:04:55").unwrap();
+        assert_eq!(field.key, "Uptime");
+        assert_eq!(field.value, "12:04:55");
+        for invalid in ["no separator", ":value", " :value"] {
+            assert!(
+                matches!(
+                    parse_section_field(invalid),
+                    Err(SlackCliError::InvalidSectionField(ref s)) if s == invalid
+                ),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_attach_field_valid() {
+        let field = parse_attach_field("Env=prod", false).unwrap();
+        assert_eq!(field.title, "Env");
+        assert_eq!(field.value, "prod");
+        assert!(!field.short);
+    }
+
+    #[test]
+    fn test_parse_attach_field_short() {
+        let field = parse_attach_field("Env=prod", true).unwrap();
+        assert!(field.short);
+    }
+
+    #[test]
+    fn test_parse_attach_field_splits_on_first_equals() {
+        let field = parse_attach_field("Query=a=b", false).unwrap();
+        assert_eq!(field.title, "Query");
+        assert_eq!(field.value, "a=b");
+    }
+
+    #[test]
+    fn test_parse_attach_field_missing_separator_rejected() {
+        let result = parse_attach_field("no separator", false);
+        assert!(matches!(
+            result,
+            Err(SlackCliError::InvalidAttachmentField(ref s)) if s == "no separator"
+        ));
+    }
+
+    #[test]
+    fn test_parse_attach_field_empty_title_rejected() {
+        let result = parse_attach_field("=value", false);
+        assert!(matches!(
+            result,
+            Err(SlackCliError::InvalidAttachmentField(_))
+        ));
+    }
 }
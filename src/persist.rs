@@ -0,0 +1,135 @@
+use serde::de::DeserializeOwned;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// A JSON file read back from disk. A file that no longer parses, e.g. one cut
+/// short by a crash, is loaded as missing with a `warning` so the run can go on
+/// and overwrite it.
+#[derive(Debug, PartialEq)]
+pub struct Loaded<T> {
+    pub value: Option<T>,
+    pub warning: Option<String>,
+}
+
+/// Reads and parses `path`. Only I/O errors other than a missing file are `Err`.
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> io::Result<Loaded<T>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Ok(Loaded {
+                value: None,
+                warning: None,
+            })
+        }
+        Err(e) => return Err(e),
+    };
+
+    Ok(match serde_json::from_str(&contents) {
+        Ok(value) => Loaded {
+            value: Some(value),
+            warning: None,
+        },
+        Err(e) => Loaded {
+            value: None,
+            warning: Some(format!(
+                "{}: ignoring corrupt contents ({e})",
+                path.display()
+            )),
+        },
+    })
+}
+
+/// Replaces `path` with `bytes` through a temp file in the same directory, so
+/// readers see either the old contents or the new ones, never a partial write.
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let tmp = temp_path(path);
+    let result = File::create(&tmp).and_then(|mut file| {
+        file.write_all(bytes)?;
+        file.sync_all()
+    });
+    let result = result.and_then(|()| fs::rename(&tmp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// `dir/.name.<pid>.tmp`: hidden, and distinct per process so concurrent runs
+/// don't write through each other's temp file
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{name}.{}.tmp", std::process::id()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_then_read_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("data.json");
+        write_atomic(&path, br#"{"a": 1}"#).unwrap();
+
+        let loaded: Loaded<Value> = read_json(&path).unwrap();
+        assert_eq!(loaded.value, Some(json!({"a": 1})));
+        assert_eq!(loaded.warning, None);
+        // Only the target is left behind
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_write_replaces_existing_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("data.json");
+        fs::write(&path, "old").unwrap();
+        write_atomic(&path, b"[]").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[]");
+    }
+
+    #[test]
+    fn test_read_missing_file_is_empty_without_warning() {
+        let dir = TempDir::new().unwrap();
+        let loaded: Loaded<Value> = read_json(&dir.path().join("absent.json")).unwrap();
+        assert_eq!(
+            loaded,
+            Loaded {
+                value: None,
+                warning: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_read_truncated_file_recovers_with_warning() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("data.json");
+        // What a crash halfway through a non-atomic write leaves behind
+        fs::write(&path, r#"{"version": 1, "outc"#).unwrap();
+
+        let loaded: Loaded<Value> = read_json(&path).unwrap();
+        assert_eq!(loaded.value, None);
+        let warning = loaded.warning.unwrap();
+        assert!(warning.contains("data.json"));
+        assert!(warning.contains("corrupt"));
+
+        // The next write repairs it
+        write_atomic(&path, b"{}").unwrap();
+        let loaded: Loaded<Value> = read_json(&path).unwrap();
+        assert_eq!(loaded.value, Some(json!({})));
+    }
+
+    #[test]
+    fn test_write_into_missing_directory_fails_cleanly() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("missing").join("data.json");
+        assert!(write_atomic(&path, b"{}").is_err());
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,615 @@
+use crate::log::log_event;
+use crate::slack::SlackResponse;
+use crate::time::{Clock, Sleeper};
+use crate::SlackCliError;
+use std::time::Duration;
+
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Used when a 429 response carries no usable `Retry-After` header
+pub const DEFAULT_RETRY_AFTER_SECS: u64 = 1;
+
+/// First wait before retrying a 5xx or a transient Slack error
+pub const DEFAULT_BACKOFF_SECS: u64 = 1;
+
+pub struct RetryPolicy {
+    /// Retries allowed after the first attempt
+    pub max_retries: u32,
+    /// Keep waiting out rate limits with no attempt cap
+    pub wait_for_rate_limit: bool,
+    /// Give up once the next sleep would run past this much total time
+    pub deadline: Option<Duration>,
+    /// Upper bound on a single `Retry-After` sleep
+    pub max_delay: Option<Duration>,
+    /// Random extra delay of up to this much added to each wait, so concurrent
+    /// senders don't all retry at the same instant
+    pub jitter: Option<Duration>,
+    /// Fixed seed for the jitter sequence; seeded from the clock when unset
+    pub jitter_seed: Option<u64>,
+    /// Also retry HTTP 5xx responses, with exponential backoff
+    pub retry_server_errors: bool,
+    /// First backoff wait after a 5xx or retryable Slack error, doubled on each retry
+    pub backoff: Duration,
+    /// Slack `error` codes worth retrying, e.g. `internal_error`
+    pub retry_slack_errors: Vec<String>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: DEFAULT_MAX_RETRIES,
+            wait_for_rate_limit: false,
+            deadline: None,
+            max_delay: None,
+            jitter: None,
+            jitter_seed: None,
+            retry_server_errors: false,
+            backoff: Duration::from_secs(DEFAULT_BACKOFF_SECS),
+            retry_slack_errors: Vec::new(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// `backoff` doubled `retries` times
+    fn backoff_after(&self, retries: u32) -> Duration {
+        self.backoff.saturating_mul(1 << retries.min(16))
+    }
+}
+
+/// Slack errors that signal a transient problem on Slack's side
+pub const TRANSIENT_SLACK_ERRORS: &[&str] = &[
+    "internal_error",
+    "fatal_error",
+    "service_unavailable",
+    "request_timeout",
+];
+
+/// Presets for `--retry-profile`; individual retry flags still override them
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RetryProfile {
+    /// A single attempt
+    None,
+    /// Retry 429s and 5xx a couple of times, with short waits
+    Conservative,
+    /// Retry 429s, 5xx and transient Slack errors many times, with long waits
+    Aggressive,
+}
+
+impl std::str::FromStr for RetryProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Ok(RetryProfile::None),
+            "conservative" => Ok(RetryProfile::Conservative),
+            "aggressive" => Ok(RetryProfile::Aggressive),
+            _ => Err(format!(
+                "unknown retry profile '{s}' (expected none, conservative or aggressive)"
+            )),
+        }
+    }
+}
+
+impl RetryProfile {
+    pub fn policy(self) -> RetryPolicy {
+        match self {
+            RetryProfile::None => RetryPolicy {
+                max_retries: 0,
+                ..Default::default()
+            },
+            RetryProfile::Conservative => RetryPolicy {
+                max_retries: 2,
+                max_delay: Some(Duration::from_secs(10)),
+                retry_server_errors: true,
+                backoff: Duration::from_secs(1),
+                ..Default::default()
+            },
+            RetryProfile::Aggressive => RetryPolicy {
+                max_retries: 6,
+                max_delay: Some(Duration::from_secs(60)),
+                jitter: Some(Duration::from_millis(500)),
+                retry_server_errors: true,
+                backoff: Duration::from_secs(2),
+                retry_slack_errors: TRANSIENT_SLACK_ERRORS
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// SplitMix64: tiny, seedable and plenty random for spreading out retries
+struct JitterRng(u64);
+
+impl JitterRng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly distributed duration in `0..=max`, at millisecond resolution
+    fn up_to(&mut self, max: Duration) -> Duration {
+        let max_ms = max.as_millis() as u64;
+        Duration::from_millis(self.next_u64() % (max_ms + 1))
+    }
+}
+
+pub enum Attempt {
+    Done(SlackResponse),
+    RateLimited { retry_after: Option<u64> },
+    ServerError { status: u16, body: String },
+}
+
+/// What to report if the attempt that just failed turns out to be the last
+enum LastFailure {
+    RateLimited(u64),
+    ServerError { status: u16, body: String },
+    Response(SlackResponse),
+}
+
+pub fn run_with_retry<F>(
+    policy: &RetryPolicy,
+    clock: &dyn Clock,
+    sleeper: &dyn Sleeper,
+    mut attempt: F,
+) -> Result<SlackResponse, SlackCliError>
+where
+    F: FnMut() -> Result<Attempt, SlackCliError>,
+{
+    let start = clock.now();
+    let mut retries = 0;
+    let mut rng = JitterRng(policy.jitter_seed.unwrap_or_else(|| {
+        start
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64
+    }));
+
+    loop {
+        let (wait, failure) = match attempt()? {
+            Attempt::Done(response) => {
+                let transient = !response.ok
+                    && response
+                        .error
+                        .as_ref()
+                        .is_some_and(|e| policy.retry_slack_errors.contains(e));
+                if !transient {
+                    return Ok(response);
+                }
+                (
+                    policy.backoff_after(retries),
+                    LastFailure::Response(response),
+                )
+            }
+            Attempt::RateLimited { retry_after } => {
+                let retry_after = retry_after.unwrap_or(DEFAULT_RETRY_AFTER_SECS);
+                (
+                    Duration::from_secs(retry_after),
+                    LastFailure::RateLimited(retry_after),
+                )
+            }
+            Attempt::ServerError { status, body } => {
+                if !policy.retry_server_errors {
+                    return Err(SlackCliError::ServerError { status, body });
+                }
+                (
+                    policy.backoff_after(retries),
+                    LastFailure::ServerError { status, body },
+                )
+            }
+        };
+
+        let wait = policy.jitter.map_or(wait, |max| wait + rng.up_to(max));
+        let wait = policy.max_delay.map_or(wait, |max| wait.min(max));
+        // Only rate limits are waited out indefinitely
+        let unlimited =
+            policy.wait_for_rate_limit && matches!(failure, LastFailure::RateLimited(_));
+        let attempts_left = unlimited || retries < policy.max_retries;
+        let within_deadline = policy.deadline.is_none_or(|deadline| {
+            let elapsed = clock.now().duration_since(start).unwrap_or_default();
+            elapsed + wait <= deadline
+        });
+
+        if !attempts_left || !within_deadline {
+            log_event!(WARN, "retries exhausted; giving up", retries = %retries);
+            return match failure {
+                LastFailure::RateLimited(retry_after) => {
+                    Err(SlackCliError::RateLimited(retry_after))
+                }
+                LastFailure::ServerError { status, body } => {
+                    Err(SlackCliError::ServerError { status, body })
+                }
+                // The caller reports the Slack error as usual
+                LastFailure::Response(response) => Ok(response),
+            };
+        }
+
+        log_event!(
+            INFO,
+            "retrying",
+            wait_ms = %wait.as_millis(),
+            retries = %retries,
+        );
+        sleeper.sleep(wait);
+        retries += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// Records requested sleeps and advances its own clock instead of blocking
+    struct FakeTime {
+        now: Cell<SystemTime>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl FakeTime {
+        fn new() -> Self {
+            FakeTime {
+                now: Cell::new(UNIX_EPOCH),
+                sleeps: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn sleeps(&self) -> Vec<Duration> {
+            self.sleeps.borrow().clone()
+        }
+    }
+
+    impl Clock for FakeTime {
+        fn now(&self) -> SystemTime {
+            self.now.get()
+        }
+    }
+
+    impl Sleeper for FakeTime {
+        fn sleep(&self, duration: Duration) {
+            self.sleeps.borrow_mut().push(duration);
+            self.now.set(self.now.get() + duration);
+        }
+    }
+
+    fn ok_response() -> SlackResponse {
+        SlackResponse {
+            ok: true,
+            ..Default::default()
+        }
+    }
+
+    /// Replays `Retry-After` values (None = header missing) as 429s, then succeeds
+    fn scripted_sequence(
+        retry_afters: Vec<Option<u64>>,
+    ) -> impl FnMut() -> Result<Attempt, SlackCliError> {
+        let mut remaining = retry_afters.into_iter();
+        move || match remaining.next() {
+            Some(retry_after) => Ok(Attempt::RateLimited { retry_after }),
+            None => Ok(Attempt::Done(ok_response())),
+        }
+    }
+
+    /// Rate-limits the first `limited` attempts, then succeeds
+    fn scripted(limited: u32, retry_after: u64) -> impl FnMut() -> Result<Attempt, SlackCliError> {
+        scripted_sequence(vec![Some(retry_after); limited as usize])
+    }
+
+    fn secs(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|s| Duration::from_secs(*s)).collect()
+    }
+
+    #[test]
+    fn test_records_sleeps_for_scripted_429_sequence() {
+        let time = FakeTime::new();
+        let attempt = scripted_sequence(vec![Some(2), Some(5), None]);
+        let result = run_with_retry(&RetryPolicy::default(), &time, &time, attempt);
+        assert!(result.unwrap().ok);
+        assert_eq!(time.sleeps(), secs(&[2, 5, DEFAULT_RETRY_AFTER_SECS]));
+        assert_eq!(time.now(), UNIX_EPOCH + Duration::from_secs(8));
+    }
+
+    #[test]
+    fn test_succeeds_within_max_retries() {
+        let time = FakeTime::new();
+        let result = run_with_retry(&RetryPolicy::default(), &time, &time, scripted(2, 3));
+        assert!(result.unwrap().ok);
+        assert_eq!(time.sleeps(), secs(&[3, 3]));
+    }
+
+    #[test]
+    fn test_exhausted_retries_return_rate_limited() {
+        let time = FakeTime::new();
+        let result = run_with_retry(&RetryPolicy::default(), &time, &time, scripted(10, 7));
+        assert!(matches!(result, Err(SlackCliError::RateLimited(7))));
+        assert_eq!(time.sleeps().len(), DEFAULT_MAX_RETRIES as usize);
+    }
+
+    #[test]
+    fn test_wait_for_rate_limit_ignores_max_retries() {
+        let time = FakeTime::new();
+        let policy = RetryPolicy {
+            max_retries: 0,
+            wait_for_rate_limit: true,
+            ..Default::default()
+        };
+        let result = run_with_retry(&policy, &time, &time, scripted(25, 1));
+        assert!(result.unwrap().ok);
+        assert_eq!(time.sleeps().len(), 25);
+    }
+
+    #[test]
+    fn test_wait_for_rate_limit_honors_deadline() {
+        let time = FakeTime::new();
+        let policy = RetryPolicy {
+            max_retries: 0,
+            wait_for_rate_limit: true,
+            deadline: Some(Duration::from_secs(10)),
+            ..Default::default()
+        };
+        let result = run_with_retry(&policy, &time, &time, scripted(100, 4));
+        assert!(matches!(result, Err(SlackCliError::RateLimited(4))));
+        assert_eq!(time.sleeps(), secs(&[4, 4]));
+    }
+
+    #[test]
+    fn test_max_delay_clamps_large_retry_after() {
+        let time = FakeTime::new();
+        let policy = RetryPolicy {
+            max_delay: Some(Duration::from_secs(5)),
+            ..Default::default()
+        };
+        let result = run_with_retry(&policy, &time, &time, scripted(2, 45));
+        assert!(result.unwrap().ok);
+        assert_eq!(time.sleeps(), secs(&[5, 5]));
+    }
+
+    #[test]
+    fn test_max_delay_clamped_attempts_still_count() {
+        let time = FakeTime::new();
+        let policy = RetryPolicy {
+            max_retries: 2,
+            max_delay: Some(Duration::from_secs(1)),
+            ..Default::default()
+        };
+        let result = run_with_retry(&policy, &time, &time, scripted(10, 60));
+        assert!(matches!(result, Err(SlackCliError::RateLimited(60))));
+        assert_eq!(time.sleeps(), secs(&[1, 1]));
+    }
+
+    #[test]
+    fn test_max_delay_above_retry_after_is_noop() {
+        let time = FakeTime::new();
+        let policy = RetryPolicy {
+            max_delay: Some(Duration::from_secs(30)),
+            ..Default::default()
+        };
+        run_with_retry(&policy, &time, &time, scripted(1, 2)).unwrap();
+        assert_eq!(time.sleeps(), secs(&[2]));
+    }
+
+    #[test]
+    fn test_same_jitter_seed_gives_identical_delays() {
+        let policy = RetryPolicy {
+            jitter: Some(Duration::from_millis(500)),
+            jitter_seed: Some(42),
+            ..Default::default()
+        };
+        let first = FakeTime::new();
+        run_with_retry(&policy, &first, &first, scripted(3, 1)).unwrap();
+        let second = FakeTime::new();
+        run_with_retry(&policy, &second, &second, scripted(3, 1)).unwrap();
+
+        assert_eq!(first.sleeps(), second.sleeps());
+        assert_ne!(first.sleeps(), secs(&[1, 1, 1]));
+    }
+
+    #[test]
+    fn test_different_jitter_seeds_differ() {
+        let run = |seed| {
+            let policy = RetryPolicy {
+                jitter: Some(Duration::from_millis(1000)),
+                jitter_seed: Some(seed),
+                ..Default::default()
+            };
+            let time = FakeTime::new();
+            run_with_retry(&policy, &time, &time, scripted(3, 1)).unwrap();
+            time.sleeps()
+        };
+        assert_ne!(run(1), run(2));
+    }
+
+    #[test]
+    fn test_jitter_is_bounded_and_clamped() {
+        let policy = RetryPolicy {
+            max_retries: 20,
+            jitter: Some(Duration::from_millis(300)),
+            jitter_seed: Some(7),
+            ..Default::default()
+        };
+        let time = FakeTime::new();
+        run_with_retry(&policy, &time, &time, scripted(20, 2)).unwrap();
+        for sleep in time.sleeps() {
+            assert!(sleep >= Duration::from_secs(2));
+            assert!(sleep <= Duration::from_millis(2300));
+        }
+
+        let clamped = RetryPolicy {
+            max_delay: Some(Duration::from_secs(2)),
+            ..policy
+        };
+        let time = FakeTime::new();
+        run_with_retry(&clamped, &time, &time, scripted(5, 2)).unwrap();
+        assert_eq!(time.sleeps(), secs(&[2, 2, 2, 2, 2]));
+    }
+
+    fn slack_error(error: &str) -> SlackResponse {
+        SlackResponse {
+            ok: false,
+            error: Some(error.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Plays back `attempts`, then succeeds
+    fn replay(attempts: Vec<Attempt>) -> impl FnMut() -> Result<Attempt, SlackCliError> {
+        let mut remaining = attempts.into_iter();
+        move || Ok(remaining.next().unwrap_or(Attempt::Done(ok_response())))
+    }
+
+    fn server_error(status: u16) -> Attempt {
+        Attempt::ServerError {
+            status,
+            body: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_server_errors_fail_fast_by_default() {
+        let time = FakeTime::new();
+        let attempt = replay(vec![server_error(503)]);
+        let result = run_with_retry(&RetryPolicy::default(), &time, &time, attempt);
+        assert!(matches!(
+            result,
+            Err(SlackCliError::ServerError { status: 503, .. })
+        ));
+        assert!(time.sleeps().is_empty());
+    }
+
+    #[test]
+    fn test_server_errors_back_off_exponentially() {
+        let time = FakeTime::new();
+        let policy = RetryPolicy {
+            retry_server_errors: true,
+            backoff: Duration::from_secs(1),
+            ..Default::default()
+        };
+        let attempt = replay(vec![
+            server_error(500),
+            server_error(502),
+            server_error(503),
+        ]);
+        assert!(run_with_retry(&policy, &time, &time, attempt).unwrap().ok);
+        assert_eq!(time.sleeps(), secs(&[1, 2, 4]));
+    }
+
+    #[test]
+    fn test_exhausted_server_error_retries() {
+        let time = FakeTime::new();
+        let policy = RetryPolicy {
+            max_retries: 1,
+            retry_server_errors: true,
+            ..Default::default()
+        };
+        let attempt = || {
+            Ok(Attempt::ServerError {
+                status: 502,
+                body: "upstream timed out".to_string(),
+            })
+        };
+        let result = run_with_retry(&policy, &time, &time, attempt);
+        assert!(matches!(
+            result,
+            Err(SlackCliError::ServerError { status: 502, ref body }) if body == "upstream timed out"
+        ));
+        assert_eq!(time.sleeps().len(), 1);
+    }
+
+    #[test]
+    fn test_listed_slack_errors_are_retried() {
+        let time = FakeTime::new();
+        let policy = RetryPolicy {
+            retry_slack_errors: vec!["internal_error".to_string()],
+            ..Default::default()
+        };
+        let attempt = replay(vec![Attempt::Done(slack_error("internal_error"))]);
+        assert!(run_with_retry(&policy, &time, &time, attempt).unwrap().ok);
+        assert_eq!(time.sleeps(), secs(&[DEFAULT_BACKOFF_SECS]));
+
+        // Other errors are returned untouched
+        let time = FakeTime::new();
+        let attempt = replay(vec![Attempt::Done(slack_error("channel_not_found"))]);
+        let response = run_with_retry(&policy, &time, &time, attempt).unwrap();
+        assert_eq!(response.error.as_deref(), Some("channel_not_found"));
+        assert!(time.sleeps().is_empty());
+    }
+
+    #[test]
+    fn test_exhausted_slack_error_retries_return_last_response() {
+        let time = FakeTime::new();
+        let policy = RetryPolicy {
+            max_retries: 2,
+            retry_slack_errors: vec!["fatal_error".to_string()],
+            ..Default::default()
+        };
+        let attempt = move || Ok(Attempt::Done(slack_error("fatal_error")));
+        let response = run_with_retry(&policy, &time, &time, attempt).unwrap();
+        assert_eq!(response.error.as_deref(), Some("fatal_error"));
+        assert_eq!(time.sleeps().len(), 2);
+    }
+
+    #[test]
+    fn test_wait_for_rate_limit_does_not_extend_server_error_retries() {
+        let time = FakeTime::new();
+        let policy = RetryPolicy {
+            max_retries: 0,
+            wait_for_rate_limit: true,
+            retry_server_errors: true,
+            ..Default::default()
+        };
+        let attempt = replay(vec![server_error(500)]);
+        let result = run_with_retry(&policy, &time, &time, attempt);
+        assert!(matches!(
+            result,
+            Err(SlackCliError::ServerError { status: 500, .. })
+        ));
+    }
+
+    #[test]
+    fn test_retry_profile_none() {
+        let policy = RetryProfile::None.policy();
+        assert_eq!(policy.max_retries, 0);
+        assert!(!policy.retry_server_errors);
+        assert!(policy.retry_slack_errors.is_empty());
+    }
+
+    #[test]
+    fn test_retry_profile_conservative() {
+        let policy = RetryProfile::Conservative.policy();
+        assert_eq!(policy.max_retries, 2);
+        assert!(policy.retry_server_errors);
+        assert_eq!(policy.backoff, Duration::from_secs(1));
+        assert_eq!(policy.max_delay, Some(Duration::from_secs(10)));
+        assert_eq!(policy.jitter, None);
+        assert!(policy.retry_slack_errors.is_empty());
+        assert!(!policy.wait_for_rate_limit);
+    }
+
+    #[test]
+    fn test_retry_profile_aggressive() {
+        let policy = RetryProfile::Aggressive.policy();
+        assert_eq!(policy.max_retries, 6);
+        assert!(policy.retry_server_errors);
+        assert_eq!(policy.backoff, Duration::from_secs(2));
+        assert_eq!(policy.max_delay, Some(Duration::from_secs(60)));
+        assert_eq!(policy.jitter, Some(Duration::from_millis(500)));
+        assert_eq!(policy.retry_slack_errors, TRANSIENT_SLACK_ERRORS);
+    }
+
+    #[test]
+    fn test_retry_profile_from_str() {
+        assert_eq!("none".parse::<RetryProfile>(), Ok(RetryProfile::None));
+        assert_eq!(
+            "Aggressive".parse::<RetryProfile>(),
+            Ok(RetryProfile::Aggressive)
+        );
+        assert!("reckless".parse::<RetryProfile>().is_err());
+    }
+}
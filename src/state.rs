@@ -0,0 +1,224 @@
+use crate::persist::{self, Loaded};
+use crate::{SendConfig, SlackCliError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Bumped whenever the state file layout changes incompatibly
+pub const STATE_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Success,
+    Failure,
+}
+
+/// What `--state-file` remembers about the previous run:
+///
+/// ```json
+/// {"version": 1, "outcome": "success", "content_hash": "9f2c1a7b03d4e5f6", "updated_at": 1700000000}
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SendState {
+    pub version: u32,
+    pub outcome: Outcome,
+    /// FNV-1a 64 of the message content, as 16 hex digits
+    pub content_hash: String,
+    /// Unix epoch seconds of the run that wrote this state
+    pub updated_at: u64,
+}
+
+impl SendState {
+    pub fn new(outcome: Outcome, content_hash: u64, now: SystemTime) -> Self {
+        SendState {
+            version: STATE_VERSION,
+            outcome,
+            content_hash: format!("{content_hash:016x}"),
+            updated_at: now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+        }
+    }
+}
+
+/// Sending is skipped only when the last run succeeded with identical content.
+/// A missing state, a previous failure, or changed content all send.
+pub fn should_send(previous: Option<&SendState>, content_hash: u64) -> bool {
+    match previous {
+        Some(state) => {
+            state.outcome == Outcome::Failure
+                || state.content_hash != format!("{content_hash:016x}")
+        }
+        None => true,
+    }
+}
+
+/// Reads the state file. A missing file loads as `None`, and so does a corrupt
+/// one (with a warning) so that the next write replaces it. Unreadable files and
+/// an unknown `version` are `StateFileError`.
+pub fn read_state(path: &Path) -> Result<Loaded<SendState>, SlackCliError> {
+    let loaded: Loaded<SendState> = persist::read_json(path)
+        .map_err(|e| SlackCliError::StateFileError(format!("{}: {e}", path.display())))?;
+    if let Some(ref state) = loaded.value {
+        if state.version != STATE_VERSION {
+            return Err(SlackCliError::StateFileError(format!(
+                "{}: unsupported version {}",
+                path.display(),
+                state.version
+            )));
+        }
+    }
+    Ok(loaded)
+}
+
+/// Writes the state atomically so a crash never leaves it half-written.
+pub fn write_state(path: &Path, state: &SendState) -> Result<(), SlackCliError> {
+    let json = serde_json::to_vec_pretty(state)
+        .map_err(|e| SlackCliError::SerializationError(e.to_string()))?;
+    persist::write_atomic(path, &json)
+        .map_err(|e| SlackCliError::StateFileError(format!("{}: {e}", path.display())))
+}
+
+/// Hashes everything that changes what lands in the channel.
+pub fn content_hash(config: &SendConfig) -> u64 {
+    let blocks = config
+        .blocks
+        .as_ref()
+        .map(|b| serde_json::Value::Array(b.clone()).to_string())
+        .unwrap_or_default();
+    let parts = [
+        config.channel.as_str(),
+        config.title.as_deref().unwrap_or_default(),
+        config.color.as_deref().unwrap_or_default(),
+        config.message.as_str(),
+        blocks.as_str(),
+    ];
+    // Separate with NUL so ("ab", "c") and ("a", "bc") hash differently
+    fnv1a_64(parts.join("\0").as_bytes())
+}
+
+pub(crate) fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn at(epoch: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(epoch)
+    }
+
+    fn config(message: &str) -> SendConfig {
+        SendConfig {
+            channel: "#ops".to_string(),
+            message: message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_fnv1a_64_known_vectors() {
+        assert_eq!(fnv1a_64(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a_64(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_should_send_without_previous_state() {
+        assert!(should_send(None, 42));
+    }
+
+    #[test]
+    fn test_should_send_transitions() {
+        let hash = content_hash(&config("all good"));
+        let success = SendState::new(Outcome::Success, hash, at(0));
+        let failure = SendState::new(Outcome::Failure, hash, at(0));
+
+        // Same content after a success is suppressed
+        assert!(!should_send(Some(&success), hash));
+        // Same content after a failure is retried
+        assert!(should_send(Some(&failure), hash));
+        // Changed content always sends
+        let changed = content_hash(&config("recovered"));
+        assert!(should_send(Some(&success), changed));
+    }
+
+    #[test]
+    fn test_content_hash_covers_channel_and_title() {
+        let base = config("msg");
+        let mut other_channel = config("msg");
+        other_channel.channel = "#alerts".to_string();
+        let mut titled = config("msg");
+        titled.title = Some("Title".to_string());
+        assert_ne!(content_hash(&base), content_hash(&other_channel));
+        assert_ne!(content_hash(&base), content_hash(&titled));
+        assert_eq!(content_hash(&base), content_hash(&config("msg")));
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("state.json");
+        let state = SendState::new(Outcome::Success, 0xdead_beef, at(1_700_000_000));
+        write_state(&path, &state).unwrap();
+        assert_eq!(read_state(&path).unwrap().value, Some(state));
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_state_schema() {
+        let state = SendState::new(Outcome::Failure, 0xff, at(1_700_000_000));
+        let json = serde_json::to_value(&state).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "version": 1,
+                "outcome": "failure",
+                "content_hash": "00000000000000ff",
+                "updated_at": 1_700_000_000
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_missing_state_is_none() {
+        let dir = TempDir::new().unwrap();
+        let loaded = read_state(&dir.path().join("absent.json")).unwrap();
+        assert_eq!(loaded.value, None);
+        assert_eq!(loaded.warning, None);
+    }
+
+    #[test]
+    fn test_read_corrupt_state_recovers_as_missing() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("state.json");
+        fs::write(&path, r#"{"version": 1, "outcome": "succ"#).unwrap();
+
+        let loaded = read_state(&path).unwrap();
+        assert_eq!(loaded.value, None);
+        assert!(loaded.warning.unwrap().contains("corrupt"));
+        // With no previous state, the run sends and then rewrites the file
+        assert!(should_send(loaded.value.as_ref(), 42));
+    }
+
+    #[test]
+    fn test_read_unknown_version_is_error() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("state.json");
+        fs::write(
+            &path,
+            r#"{"version": 99, "outcome": "success", "content_hash": "00", "updated_at": 0}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            read_state(&path),
+            Err(SlackCliError::StateFileError(ref msg)) if msg.contains("version 99")
+        ));
+    }
+}
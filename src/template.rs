@@ -0,0 +1,95 @@
+use crate::host::{resolve_hostname, HostnameSource};
+use crate::SlackCliError;
+
+/// Expands `{{hostname}}` and `{{env:VAR}}` tokens in a config value, e.g.
+/// `"deploy@{{hostname}} ({{env:DEPLOY_ENV}})"` becomes `"deploy@web01 (prod)"`.
+/// Unknown tokens, unset variables and unclosed `{{` are errors rather than
+/// being posted literally.
+pub fn render(
+    template: &str,
+    hostname: &dyn HostnameSource,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<String, SlackCliError> {
+    let invalid =
+        |msg: String| SlackCliError::InvalidConfig(format!("template '{template}': {msg}"));
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| invalid("unclosed '{{'".to_string()))?;
+        let token = after[..end].trim();
+
+        if token == "hostname" {
+            out.push_str(&resolve_hostname(hostname));
+        } else if let Some(var) = token.strip_prefix("env:") {
+            let value = env(var).ok_or_else(|| invalid(format!("{var} is not set")))?;
+            out.push_str(&value);
+        } else {
+            return Err(invalid(format!("unknown token '{{{{{token}}}}}'")));
+        }
+        rest = &after[end + 2..];
+    }
+
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host::FixedHostname;
+
+    fn env(key: &str) -> Option<String> {
+        (key == "DEPLOY_ENV").then(|| "prod".to_string())
+    }
+
+    #[test]
+    fn test_render_hostname_and_env() {
+        let rendered = render(
+            "deploy@{{hostname}} ({{env:DEPLOY_ENV}})",
+            &FixedHostname(Some("web01")),
+            env,
+        )
+        .unwrap();
+        assert_eq!(rendered, "deploy@web01 (prod)");
+    }
+
+    #[test]
+    fn test_render_without_tokens_is_unchanged() {
+        let rendered = render("Deploy Bot", &FixedHostname(None), env).unwrap();
+        assert_eq!(rendered, "Deploy Bot");
+    }
+
+    #[test]
+    fn test_render_unknown_hostname() {
+        let rendered = render("bot@{{ hostname }}", &FixedHostname(None), env).unwrap();
+        assert_eq!(rendered, "bot@unknown");
+    }
+
+    #[test]
+    fn test_render_unset_env_is_error() {
+        let result = render("{{env:MISSING}}", &FixedHostname(None), env);
+        assert!(matches!(
+            result,
+            Err(SlackCliError::InvalidConfig(ref msg)) if msg.contains("MISSING is not set")
+        ));
+    }
+
+    #[test]
+    fn test_render_unknown_token_and_unclosed_are_errors() {
+        let unknown = render("{{user}}", &FixedHostname(None), env);
+        assert!(matches!(
+            unknown,
+            Err(SlackCliError::InvalidConfig(ref msg)) if msg.contains("unknown token '{{user}}'")
+        ));
+        let unclosed = render("bot@{{hostname", &FixedHostname(None), env);
+        assert!(matches!(
+            unclosed,
+            Err(SlackCliError::InvalidConfig(ref msg)) if msg.contains("unclosed")
+        ));
+    }
+}
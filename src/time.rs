@@ -0,0 +1,203 @@
+use crate::SlackCliError;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+pub trait Sleeper {
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct ThreadSleeper;
+
+impl Sleeper for ThreadSleeper {
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// Parses either a Unix epoch in seconds or an RFC 3339 timestamp
+/// (e.g. "2024-01-15T10:30:00Z" or "2024-01-15T10:30:00.5+02:00").
+pub fn parse_timestamp(input: &str) -> Result<SystemTime, SlackCliError> {
+    let input = input.trim();
+    let invalid = || SlackCliError::InvalidTimestamp(input.to_string());
+
+    if !input.is_empty() && input.chars().all(|c| c.is_ascii_digit()) {
+        let secs: u64 = input.parse().map_err(|_| invalid())?;
+        return Ok(UNIX_EPOCH + Duration::from_secs(secs));
+    }
+
+    let secs = parse_rfc3339(input).ok_or_else(invalid)?;
+    if secs < 0 {
+        return Err(invalid());
+    }
+    Ok(UNIX_EPOCH + Duration::from_secs(secs as u64))
+}
+
+fn parse_rfc3339(input: &str) -> Option<i64> {
+    let (date, rest) = input.split_once(['T', 't', ' '])?;
+
+    let mut date_parts = date.splitn(3, '-');
+    let year: i64 = parse_digits(date_parts.next()?, 4)?;
+    let month: i64 = parse_digits(date_parts.next()?, 2)?;
+    let day: i64 = parse_digits(date_parts.next()?, 2)?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let (time, offset_secs) = if let Some(time) = rest.strip_suffix(['Z', 'z']) {
+        (time, 0)
+    } else {
+        let sign_pos = rest.rfind(['+', '-'])?;
+        let (time, offset) = rest.split_at(sign_pos);
+        let sign = if offset.starts_with('-') { -1 } else { 1 };
+        let (oh, om) = offset[1..].split_once(':')?;
+        let offset_secs = parse_digits(oh, 2)? * 3600 + parse_digits(om, 2)? * 60;
+        (time, sign * offset_secs)
+    };
+
+    // Fractional seconds are accepted but ignored
+    let time = time.split_once('.').map_or(time, |(whole, _)| whole);
+    let mut time_parts = time.splitn(3, ':');
+    let hour: i64 = parse_digits(time_parts.next()?, 2)?;
+    let minute: i64 = parse_digits(time_parts.next()?, 2)?;
+    let second: i64 = parse_digits(time_parts.next()?, 2)?;
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    let days = days_from_civil(year, month, day);
+    Some(days * 86400 + hour * 3600 + minute * 60 + second - offset_secs)
+}
+
+fn parse_digits(s: &str, len: usize) -> Option<i64> {
+    if s.len() != len || !s.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+// Howard Hinnant's days_from_civil: days since 1970-01-01 for a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Formats a duration using its largest whole unit: "42s", "5m", "3h", "2d".
+pub fn humanize_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86399 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86400),
+    }
+}
+
+/// Builds the "(updated 5m ago)" footer text for a `--since` timestamp.
+/// Timestamps in the future are treated as "0s ago".
+pub fn since_footer(since: &str, clock: &dyn Clock) -> Result<String, SlackCliError> {
+    let instant = parse_timestamp(since)?;
+    let elapsed = clock.now().duration_since(instant).unwrap_or_default();
+    Ok(format!("(updated {} ago)", humanize_duration(elapsed)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedClock(SystemTime);
+
+    impl Clock for FixedClock {
+        fn now(&self) -> SystemTime {
+            self.0
+        }
+    }
+
+    fn clock_at(epoch: u64) -> FixedClock {
+        FixedClock(UNIX_EPOCH + Duration::from_secs(epoch))
+    }
+
+    #[test]
+    fn test_parse_timestamp_epoch() {
+        let parsed = parse_timestamp("1700000000").unwrap();
+        assert_eq!(parsed, UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+    }
+
+    #[test]
+    fn test_parse_timestamp_rfc3339_utc() {
+        let parsed = parse_timestamp("2023-11-14T22:13:20Z").unwrap();
+        assert_eq!(parsed, UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+    }
+
+    #[test]
+    fn test_parse_timestamp_rfc3339_with_offset_and_fraction() {
+        let parsed = parse_timestamp("2023-11-15T00:13:20.250+02:00").unwrap();
+        assert_eq!(parsed, UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+    }
+
+    #[test]
+    fn test_parse_timestamp_invalid() {
+        assert!(matches!(
+            parse_timestamp("yesterday"),
+            Err(SlackCliError::InvalidTimestamp(ref s)) if s == "yesterday"
+        ));
+        assert!(matches!(
+            parse_timestamp("2023-13-01T00:00:00Z"),
+            Err(SlackCliError::InvalidTimestamp(_))
+        ));
+    }
+
+    #[test]
+    fn test_humanize_duration_units() {
+        assert_eq!(humanize_duration(Duration::from_secs(42)), "42s");
+        assert_eq!(humanize_duration(Duration::from_secs(300)), "5m");
+        assert_eq!(humanize_duration(Duration::from_secs(3 * 3600 + 59)), "3h");
+        assert_eq!(humanize_duration(Duration::from_secs(2 * 86400)), "2d");
+    }
+
+    #[test]
+    fn test_since_footer_minutes() {
+        let footer = since_footer("1700000000", &clock_at(1_700_000_300)).unwrap();
+        assert_eq!(footer, "(updated 5m ago)");
+    }
+
+    #[test]
+    fn test_since_footer_seconds_and_days() {
+        let clock = clock_at(1_700_000_000);
+        assert_eq!(
+            since_footer("1699999990", &clock).unwrap(),
+            "(updated 10s ago)"
+        );
+        assert_eq!(
+            since_footer("1699740800", &clock).unwrap(),
+            "(updated 3d ago)"
+        );
+    }
+
+    #[test]
+    fn test_since_footer_future_is_zero() {
+        let footer = since_footer("1700000100", &clock_at(1_700_000_000)).unwrap();
+        assert_eq!(footer, "(updated 0s ago)");
+    }
+
+    #[test]
+    fn test_since_footer_invalid_timestamp() {
+        let result = since_footer("not-a-time", &clock_at(1_700_000_000));
+        assert!(matches!(result, Err(SlackCliError::InvalidTimestamp(_))));
+    }
+}
@@ -0,0 +1,570 @@
+use crate::SlackCliError;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Element types that only work inside an `input` block, i.e. in modals and App Home
+const INPUT_ONLY_ELEMENTS: &[&str] = &[
+    "plain_text_input",
+    "rich_text_input",
+    "file_input",
+    "email_text_input",
+    "url_text_input",
+    "number_input",
+];
+
+/// Rejects `input` blocks and input-only elements, which Slack accepts in modals
+/// and App Home but fails with `invalid_blocks` in a posted message.
+pub fn check_message_surface(blocks: &[Value]) -> Result<(), SlackCliError> {
+    for (i, block) in blocks.iter().enumerate() {
+        let path = format!("blocks[{i}]");
+        if block.get("type").and_then(Value::as_str) == Some("input") {
+            return Err(surface_error(&path, "an 'input' block"));
+        }
+        if let Some((path, kind)) = find_input_element(&path, block) {
+            return Err(surface_error(&path, &format!("a '{kind}' element")));
+        }
+    }
+    Ok(())
+}
+
+fn surface_error(path: &str, what: &str) -> SlackCliError {
+    SlackCliError::InvalidBlocksJson(format!(
+        "{path} is {what}, which only works in modals and App Home, not in messages \
+         (pass --allow-input-blocks to send it anyway)"
+    ))
+}
+
+fn find_input_element(path: &str, value: &Value) -> Option<(String, String)> {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(kind)) = map.get("type") {
+                if INPUT_ONLY_ELEMENTS.contains(&kind.as_str()) {
+                    return Some((path.to_string(), kind.clone()));
+                }
+            }
+            map.iter()
+                .find_map(|(key, child)| find_input_element(&format!("{path}.{key}"), child))
+        }
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .find_map(|(i, item)| find_input_element(&format!("{path}[{i}]"), item)),
+        _ => None,
+    }
+}
+
+/// Block types `--schema-validate` understands
+pub const SCHEMA_BLOCK_TYPES: &[&str] = &[
+    "section", "header", "divider", "context", "image", "actions",
+];
+const TEXT_TYPES: &[&str] = &["mrkdwn", "plain_text"];
+const PLAIN_TEXT: &[&str] = &["plain_text"];
+const BLOCK_ID_MAX: usize = 255;
+const SECTION_FIELDS_MAX: usize = 10;
+const CONTEXT_ELEMENTS_MAX: usize = 10;
+const ACTIONS_ELEMENTS_MAX: usize = 25;
+const HEADER_TEXT_MAX: usize = 150;
+const SECTION_TEXT_MAX: usize = 3000;
+const FIELD_TEXT_MAX: usize = 2000;
+const BUTTON_TEXT_MAX: usize = 75;
+
+/// One schema violation, located by a path such as `blocks[2].text.type`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaError {
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Checks blocks against the layout of the common Block Kit types, collecting
+/// every violation rather than stopping at the first. Unlike the checks run on
+/// every send, unknown block types are reported too.
+pub fn validate_schema(blocks: &[Value]) -> Vec<SchemaError> {
+    let mut errors = Vec::new();
+    for (i, block) in blocks.iter().enumerate() {
+        check_block(&format!("blocks[{i}]"), block, &mut errors);
+    }
+    errors
+}
+
+fn push(errors: &mut Vec<SchemaError>, path: &str, message: impl Into<String>) {
+    errors.push(SchemaError {
+        path: path.to_string(),
+        message: message.into(),
+    });
+}
+
+fn one_of(allowed: &[&str]) -> String {
+    format!("expected one of [{}]", allowed.join(", "))
+}
+
+/// Returns the object's `type`, reporting it when missing or not in `allowed`
+fn check_type<'a>(
+    path: &str,
+    object: &'a serde_json::Map<String, Value>,
+    allowed: &[&str],
+    errors: &mut Vec<SchemaError>,
+) -> Option<&'a str> {
+    let path = format!("{path}.type");
+    match object.get("type") {
+        Some(Value::String(kind)) if allowed.contains(&kind.as_str()) => Some(kind),
+        Some(Value::String(_)) => {
+            push(errors, &path, one_of(allowed));
+            None
+        }
+        Some(_) => {
+            push(errors, &path, "expected a string");
+            None
+        }
+        None => {
+            push(errors, &path, "required");
+            None
+        }
+    }
+}
+
+fn check_block(path: &str, block: &Value, errors: &mut Vec<SchemaError>) {
+    let Some(object) = block.as_object() else {
+        push(errors, path, "expected an object");
+        return;
+    };
+
+    match object.get("block_id") {
+        Some(Value::String(id)) if id.chars().count() > BLOCK_ID_MAX => push(
+            errors,
+            &format!("{path}.block_id"),
+            format!("longer than {BLOCK_ID_MAX} characters"),
+        ),
+        Some(Value::String(_)) | None => {}
+        Some(_) => push(errors, &format!("{path}.block_id"), "expected a string"),
+    }
+
+    match check_type(path, object, SCHEMA_BLOCK_TYPES, errors) {
+        Some("section") => {
+            let text = object.get("text");
+            let fields = object.get("fields");
+            if text.is_none() && fields.is_none() {
+                push(errors, path, "section requires text or fields");
+            }
+            if let Some(text) = text {
+                check_text(
+                    &format!("{path}.text"),
+                    text,
+                    TEXT_TYPES,
+                    SECTION_TEXT_MAX,
+                    errors,
+                );
+            }
+            if let Some(fields) = fields {
+                let fields_path = format!("{path}.fields");
+                if let Some(items) = check_array(&fields_path, fields, SECTION_FIELDS_MAX, errors) {
+                    for (i, field) in items.iter().enumerate() {
+                        let field_path = format!("{fields_path}[{i}]");
+                        check_text(&field_path, field, TEXT_TYPES, FIELD_TEXT_MAX, errors);
+                    }
+                }
+            }
+            if let Some(accessory) = object.get("accessory") {
+                check_element(&format!("{path}.accessory"), accessory, errors);
+            }
+        }
+        Some("header") => match object.get("text") {
+            Some(text) => check_text(
+                &format!("{path}.text"),
+                text,
+                PLAIN_TEXT,
+                HEADER_TEXT_MAX,
+                errors,
+            ),
+            None => push(errors, &format!("{path}.text"), "required"),
+        },
+        Some("context") => {
+            let elements_path = format!("{path}.elements");
+            match object.get("elements") {
+                Some(elements) => {
+                    if let Some(items) =
+                        check_array(&elements_path, elements, CONTEXT_ELEMENTS_MAX, errors)
+                    {
+                        for (i, element) in items.iter().enumerate() {
+                            let element_path = format!("{elements_path}[{i}]");
+                            if element.get("type").and_then(Value::as_str) == Some("image") {
+                                check_image(&element_path, element, errors);
+                            } else {
+                                check_text(
+                                    &element_path,
+                                    element,
+                                    TEXT_TYPES,
+                                    SECTION_TEXT_MAX,
+                                    errors,
+                                );
+                            }
+                        }
+                    }
+                }
+                None => push(errors, &elements_path, "required"),
+            }
+        }
+        Some("image") => {
+            check_image(path, block, errors);
+            if let Some(title) = object.get("title") {
+                check_text(
+                    &format!("{path}.title"),
+                    title,
+                    PLAIN_TEXT,
+                    FIELD_TEXT_MAX,
+                    errors,
+                );
+            }
+        }
+        Some("actions") => {
+            let elements_path = format!("{path}.elements");
+            match object.get("elements") {
+                Some(elements) => {
+                    if let Some(items) =
+                        check_array(&elements_path, elements, ACTIONS_ELEMENTS_MAX, errors)
+                    {
+                        for (i, element) in items.iter().enumerate() {
+                            check_element(&format!("{elements_path}[{i}]"), element, errors);
+                        }
+                    }
+                }
+                None => push(errors, &elements_path, "required"),
+            }
+        }
+        // divider has no fields of its own; unknown types were reported by check_type
+        _ => {}
+    }
+}
+
+/// A non-empty array of at most `max` items
+fn check_array<'a>(
+    path: &str,
+    value: &'a Value,
+    max: usize,
+    errors: &mut Vec<SchemaError>,
+) -> Option<&'a Vec<Value>> {
+    match value.as_array() {
+        Some(items) if items.is_empty() => {
+            push(errors, path, "expected at least 1 item");
+            None
+        }
+        Some(items) if items.len() > max => {
+            push(
+                errors,
+                path,
+                format!("expected at most {max} items, got {}", items.len()),
+            );
+            None
+        }
+        Some(items) => Some(items),
+        None => {
+            push(errors, path, "expected an array");
+            None
+        }
+    }
+}
+
+fn check_text(
+    path: &str,
+    value: &Value,
+    allowed: &[&str],
+    max: usize,
+    errors: &mut Vec<SchemaError>,
+) {
+    let Some(object) = value.as_object() else {
+        push(errors, path, "expected a text object");
+        return;
+    };
+    check_type(path, object, allowed, errors);
+    let text_path = format!("{path}.text");
+    match object.get("text") {
+        Some(Value::String(text)) if text.is_empty() => {
+            push(errors, &text_path, "must not be empty")
+        }
+        Some(Value::String(text)) if text.chars().count() > max => {
+            push(errors, &text_path, format!("longer than {max} characters"))
+        }
+        Some(Value::String(_)) => {}
+        Some(_) => push(errors, &text_path, "expected a string"),
+        None => push(errors, &text_path, "required"),
+    }
+}
+
+fn check_image(path: &str, value: &Value, errors: &mut Vec<SchemaError>) {
+    for key in ["image_url", "alt_text"] {
+        let key_path = format!("{path}.{key}");
+        match value.get(key) {
+            Some(Value::String(s)) if !s.is_empty() => {}
+            Some(Value::String(_)) => push(errors, &key_path, "must not be empty"),
+            Some(_) => push(errors, &key_path, "expected a string"),
+            None => push(errors, &key_path, "required"),
+        }
+    }
+}
+
+/// Interactive elements only need a `type`, except buttons which need a plain_text label
+fn check_element(path: &str, value: &Value, errors: &mut Vec<SchemaError>) {
+    let Some(object) = value.as_object() else {
+        push(errors, path, "expected an object");
+        return;
+    };
+    match object.get("type") {
+        Some(Value::String(kind)) if kind == "button" => match object.get("text") {
+            Some(text) => check_text(
+                &format!("{path}.text"),
+                text,
+                PLAIN_TEXT,
+                BUTTON_TEXT_MAX,
+                errors,
+            ),
+            None => push(errors, &format!("{path}.text"), "required"),
+        },
+        Some(Value::String(_)) => {}
+        Some(_) => push(errors, &format!("{path}.type"), "expected a string"),
+        None => push(errors, &format!("{path}.type"), "required"),
+    }
+}
+
+/// Rejects blocks where two interactive elements share an `action_id`,
+/// which Slack otherwise reports as an opaque `invalid_blocks` error.
+pub fn check_unique_action_ids(blocks: &[Value]) -> Result<(), SlackCliError> {
+    let mut seen = HashSet::new();
+    for block in blocks {
+        collect_action_ids(block, &mut seen)?;
+    }
+    Ok(())
+}
+
+fn collect_action_ids<'a>(
+    value: &'a Value,
+    seen: &mut HashSet<&'a str>,
+) -> Result<(), SlackCliError> {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(id)) = map.get("action_id") {
+                if !seen.insert(id) {
+                    return Err(SlackCliError::InvalidBlocksJson(format!(
+                        "duplicate action_id '{id}'"
+                    )));
+                }
+            }
+            for child in map.values() {
+                collect_action_ids(child, seen)?;
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_action_ids(item, seen)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn button(action_id: &str) -> Value {
+        json!({
+            "type": "button",
+            "text": {"type": "plain_text", "text": "Go"},
+            "action_id": action_id
+        })
+    }
+
+    #[test]
+    fn test_unique_action_ids_accepted() {
+        let blocks = vec![
+            json!({"type": "actions", "elements": [button("approve"), button("reject")]}),
+            json!({
+                "type": "section",
+                "text": {"type": "mrkdwn", "text": "More"},
+                "accessory": button("details")
+            }),
+        ];
+        assert!(check_unique_action_ids(&blocks).is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_action_ids_in_same_block_rejected() {
+        let blocks = vec![json!({"type": "actions", "elements": [button("go"), button("go")]})];
+        let result = check_unique_action_ids(&blocks);
+        assert!(matches!(
+            result,
+            Err(SlackCliError::InvalidBlocksJson(ref msg)) if msg.contains("'go'")
+        ));
+    }
+
+    #[test]
+    fn test_duplicate_action_ids_across_blocks_rejected() {
+        let blocks = vec![
+            json!({"type": "actions", "elements": [button("approve")]}),
+            json!({
+                "type": "section",
+                "text": {"type": "mrkdwn", "text": "Again"},
+                "accessory": button("approve")
+            }),
+        ];
+        let result = check_unique_action_ids(&blocks);
+        assert!(matches!(
+            result,
+            Err(SlackCliError::InvalidBlocksJson(ref msg)) if msg.contains("approve")
+        ));
+    }
+
+    #[test]
+    fn test_input_block_rejected_in_messages() {
+        let blocks = vec![
+            json!({"type": "section", "text": {"type": "mrkdwn", "text": "Why?"}}),
+            json!({
+                "type": "input",
+                "label": {"type": "plain_text", "text": "Reason"},
+                "element": {"type": "plain_text_input", "action_id": "reason"}
+            }),
+        ];
+        let result = check_message_surface(&blocks);
+        assert!(matches!(
+            result,
+            Err(SlackCliError::InvalidBlocksJson(ref msg))
+                if msg.starts_with("blocks[1] is an 'input' block") && msg.contains("--allow-input-blocks")
+        ));
+    }
+
+    #[test]
+    fn test_input_only_element_outside_input_block_rejected() {
+        let blocks = vec![json!({
+            "type": "actions",
+            "elements": [button("ok"), {"type": "plain_text_input", "action_id": "note"}]
+        })];
+        let result = check_message_surface(&blocks);
+        assert!(matches!(
+            result,
+            Err(SlackCliError::InvalidBlocksJson(ref msg))
+                if msg.starts_with("blocks[0].elements[1] is a 'plain_text_input' element")
+        ));
+    }
+
+    #[test]
+    fn test_message_interactive_elements_accepted() {
+        let blocks = vec![
+            json!({"type": "actions", "elements": [
+                button("approve"),
+                {"type": "static_select", "action_id": "env", "options": []}
+            ]}),
+            json!({"type": "divider"}),
+        ];
+        assert!(check_message_surface(&blocks).is_ok());
+    }
+
+    fn paths(blocks: &[Value]) -> Vec<String> {
+        validate_schema(blocks)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_schema_accepts_common_blocks() {
+        let blocks = vec![
+            json!({"type": "header", "text": {"type": "plain_text", "text": "Deploy"}}),
+            json!({
+                "type": "section",
+                "text": {"type": "mrkdwn", "text": "*done*"},
+                "fields": [{"type": "mrkdwn", "text": "*Env*\nprod"}],
+                "accessory": button("details")
+            }),
+            json!({"type": "divider"}),
+            json!({"type": "context", "elements": [
+                {"type": "image", "image_url": "https://example.com/a.png", "alt_text": "a"},
+                {"type": "plain_text", "text": "web01"}
+            ]}),
+            json!({"type": "image", "image_url": "https://example.com/b.png", "alt_text": "b"}),
+            json!({"type": "actions", "elements": [button("approve")]}),
+        ];
+        assert_eq!(validate_schema(&blocks), vec![]);
+    }
+
+    #[test]
+    fn test_schema_reports_bad_text_type_path() {
+        let blocks = vec![
+            json!({"type": "divider"}),
+            json!({"type": "divider"}),
+            json!({"type": "section", "text": {"type": "markdown", "text": "hi"}}),
+        ];
+        assert_eq!(
+            paths(&blocks),
+            vec!["blocks[2].text.type: expected one of [mrkdwn, plain_text]"]
+        );
+    }
+
+    #[test]
+    fn test_schema_reports_every_error() {
+        let blocks = vec![
+            json!({"type": "secton", "text": {"type": "mrkdwn", "text": "typo"}}),
+            json!({"type": "header", "text": {"type": "mrkdwn", "text": "Bold"}}),
+            json!({"type": "image", "image_url": "https://example.com/c.png"}),
+            json!({"type": "context", "elements": []}),
+            json!({"type": "actions", "elements": [{"type": "button", "action_id": "x"}]}),
+            json!("divider"),
+        ];
+        assert_eq!(
+            paths(&blocks),
+            vec![
+                "blocks[0].type: expected one of [section, header, divider, context, image, actions]",
+                "blocks[1].text.type: expected one of [plain_text]",
+                "blocks[2].alt_text: required",
+                "blocks[3].elements: expected at least 1 item",
+                "blocks[4].elements[0].text: required",
+                "blocks[5]: expected an object",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_schema_reports_nested_field_errors() {
+        let blocks = vec![json!({
+            "type": "section",
+            "block_id": "x".repeat(256),
+            "fields": [
+                {"type": "mrkdwn", "text": "ok"},
+                {"type": "mrkdwn", "text": ""},
+                {"type": "mrkdwn"}
+            ]
+        })];
+        assert_eq!(
+            paths(&blocks),
+            vec![
+                "blocks[0].block_id: longer than 255 characters",
+                "blocks[0].fields[1].text: must not be empty",
+                "blocks[0].fields[2].text: required",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_schema_section_needs_text_or_fields() {
+        let blocks = vec![json!({"type": "section"}), json!({"type": "header"})];
+        assert_eq!(
+            paths(&blocks),
+            vec![
+                "blocks[0]: section requires text or fields",
+                "blocks[1].text: required",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_blocks_without_action_ids_accepted() {
+        let blocks = vec![json!({"type": "divider"}), json!({"type": "section"})];
+        assert!(check_unique_action_ids(&blocks).is_ok());
+    }
+}